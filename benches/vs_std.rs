@@ -242,6 +242,72 @@ fn greek_1_5mb(c: &mut Criterion) {
     bench_group_sampling(c, "greek", GREEK.as_bytes(), Some(SamplingMode::Flat));
 }
 
+/// Compares `validate_utf8` on a large, mostly-Greek buffer as built with
+/// and without the `prefetch` feature. Since the feature is a compile-time
+/// switch, this group only has the one `validate_utf8` function; run it once per feature
+/// flag (`cargo bench --bench vs_std greek_1_5mb_prefetch` with and
+/// without `--features prefetch`) and compare the two reports.
+fn greek_1_5mb_prefetch(c: &mut Criterion) {
+    const GREEK: &str = include_str!("../assets/greek_1_5mb.txt");
+
+    let mut group = c.benchmark_group(format!("greek/{}/prefetch", text_size(GREEK.as_bytes())));
+    group.sampling_mode(SamplingMode::Flat);
+    group.bench_function("validate_utf8", |b| {
+        b.iter(|| validate(fast, GREEK.as_bytes()))
+    });
+}
+
+/// Exercises `validate_utf8_auto`'s `AVX2_THRESHOLD` cutover across the
+/// bundled asset spectrum (27B to 1.5MB) that the threshold's doc comment
+/// claims it was tuned against, comparing it to `validate_utf8` (which,
+/// under the `simd` feature with AVX2 detected, takes the AVX2 path at every
+/// length, and otherwise the portable path) to confirm the fixed 8192-byte
+/// cutoff doesn't leave either end of that spectrum worse off.
+fn avx2_threshold(c: &mut Criterion) {
+    const LATIN_27B: &[u8] = b"Lorem ipsum dolor sit amet.";
+    const LATIN_3KB: &str = include_str!("../assets/latin_3kb.txt");
+    const GREEK_57KB: &str = include_str!("../assets/greek_57kb.txt");
+    const CHINESE_1MB: &str = include_str!("../assets/chinese_1mb.txt");
+    const GREEK_1_5MB: &str = include_str!("../assets/greek_1_5mb.txt");
+
+    for (name, text) in [
+        ("27b", LATIN_27B),
+        ("3kb", LATIN_3KB.as_bytes()),
+        ("57kb", GREEK_57KB.as_bytes()),
+        ("1mb", CHINESE_1MB.as_bytes()),
+        ("1.5mb", GREEK_1_5MB.as_bytes()),
+    ] {
+        let mut group = c.benchmark_group(format!("avx2_threshold/{name}"));
+        group.sampling_mode(SamplingMode::Flat);
+        group.bench_function("auto", |b| {
+            b.iter(|| black_box(fast_utf8::validate_utf8_auto(black_box(text)).is_ok()))
+        });
+        group.bench_function("validate_utf8", |b| {
+            b.iter(|| black_box(fast_utf8::validate_utf8(black_box(text)).is_ok()))
+        });
+    }
+}
+
+fn stream_large_multi_mb(c: &mut Criterion) {
+    const GREEK: &str = include_str!("../assets/greek_1_5mb.txt");
+    const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+
+    let mut text = Vec::with_capacity((GREEK.len() + CHINESE.len()) * 2);
+    for _ in 0..2 {
+        text.extend_from_slice(GREEK.as_bytes());
+        text.extend_from_slice(CHINESE.as_bytes());
+    }
+
+    let mut group = c.benchmark_group(format!("stream large/{}", text_size(&text)));
+    group.sampling_mode(SamplingMode::Flat);
+    group.bench_function("default", |b| {
+        b.iter(|| black_box(fast_utf8::validate_utf8(black_box(&text)).is_ok()))
+    });
+    group.bench_function("stream_large", |b| {
+        b.iter(|| black_box(fast_utf8::validate_utf8_stream_large(black_box(&text)).is_ok()))
+    });
+}
+
 fn short_strings(c: &mut Criterion) {
     const STRINGS: &str = include_str!("../assets/short_strings.txt");
 
@@ -263,7 +329,86 @@ fn short_strings(c: &mut Criterion) {
     });
 }
 
-criterion_group!(assorted, short_strings,);
+fn short_strings_batch(c: &mut Criterion) {
+    const STRINGS: &str = include_str!("../assets/short_strings.txt");
+    let lines: Vec<&[u8]> = STRINGS.lines().map(str::as_bytes).collect();
+
+    let mut group = c.benchmark_group("short strings (up to 64B) - batch vs loop");
+    group.bench_function("loop", |b| {
+        b.iter(|| {
+            for line in &lines {
+                validate(fast, line);
+            }
+        })
+    });
+
+    group.bench_function("batch", |b| {
+        b.iter(|| black_box(fast_utf8::validate_utf8_batch_short(&lines)))
+    });
+}
+
+fn fixed_size_arrays(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fixed-size record fields");
+
+    let buf8: [u8; 8] = *b"abcdefgh";
+    group.bench_function("array/8", |b| {
+        b.iter(|| black_box(fast_utf8::validate_utf8_array(&buf8)))
+    });
+    group.bench_function("slice/8", |b| {
+        b.iter(|| black_box(fast_utf8::validate_utf8(&buf8)))
+    });
+
+    let buf16: [u8; 16] = *b"abcdefghijklmnop";
+    group.bench_function("array/16", |b| {
+        b.iter(|| black_box(fast_utf8::validate_utf8_array(&buf16)))
+    });
+    group.bench_function("slice/16", |b| {
+        b.iter(|| black_box(fast_utf8::validate_utf8(&buf16)))
+    });
+
+    let buf32: [u8; 32] = *b"abcdefghijklmnopqrstuvwxyzABCDEF";
+    group.bench_function("array/32", |b| {
+        b.iter(|| black_box(fast_utf8::validate_utf8_array(&buf32)))
+    });
+    group.bench_function("slice/32", |b| {
+        b.iter(|| black_box(fast_utf8::validate_utf8(&buf32)))
+    });
+}
+
+/// Measures how quickly `validate_utf8` reaches an error that's near the
+/// start of a large, otherwise-ASCII buffer, i.e. the "mislabeled binary
+/// data" case where most of the input past the first invalid byte will
+/// never be looked at. Errors at offset 0, 10, and 100 exercise,
+/// respectively: the immediate `buf[curr] >= 128` check before any block
+/// setup runs, and (on a typically word-aligned buffer, so no byte-wise
+/// alignment walk runs, with 8-byte words) the first and the second
+/// iteration of the 8-word block loop.
+fn invalid_heavy_early_exit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("invalid-heavy early exit");
+
+    for offset in [0usize, 10, 100] {
+        let mut buf = vec![b'a'; 4096];
+        buf[offset] = 0xFF;
+
+        group.bench_function(format!("error-at-{offset}"), |b| {
+            b.iter(|| {
+                let err = fast_utf8::validate_utf8(black_box(&buf)).unwrap_err();
+                black_box(err);
+            })
+        });
+    }
+}
+
+criterion_group!(
+    assorted,
+    short_strings,
+    short_strings_batch,
+    fixed_size_arrays,
+    invalid_heavy_early_exit,
+    stream_large_multi_mb,
+    greek_1_5mb_prefetch,
+    avx2_threshold,
+);
 
 criterion_group!(
     by_language,
@@ -1,18 +1,163 @@
-use core::{hint, mem, slice};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+use core::ops::Range;
+use core::{hint, mem, str};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::io::Read;
 
 const WORD_BYTES: usize = mem::size_of::<usize>();
 const NONASCII_MASK: usize = usize::from_ne_bytes([0x80; WORD_BYTES]);
 
+/// The width, in bytes, of the `usize` word this crate's portable
+/// block-masking scan reads at a time — exposed so callers computing
+/// their own throughput numbers from [`Statistics`] (e.g.
+/// [`Statistics::bytes_scanned_fast`]) don't have to hardcode a platform
+/// assumption to reproduce the math.
+pub const fn word_bytes() -> usize {
+    WORD_BYTES
+}
+
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct Utf8Error {
     pub valid_up_to: usize,
     pub error_len: Option<u8>,
+    /// The first byte that violated the UTF-8 grammar: the lead byte
+    /// itself for invalid-lead/invalid-continuation-shape cases, or the
+    /// offending continuation byte for range violations (overlong
+    /// encodings, surrogates, out-of-range scalars). `None` when the
+    /// buffer simply ended before a full sequence could be read, since
+    /// there is no single byte to blame for a truncated tail.
+    pub error_byte: Option<u8>,
 }
 
-#[derive(Debug, Default)]
+impl Utf8Error {
+    /// Constructs a `Utf8Error` directly from its `valid_up_to`/`error_len`
+    /// fields, with [`error_byte`](Self::error_byte) left unset, for
+    /// downstream code that needs to produce or mock a compatible error
+    /// (e.g. in tests, or when wrapping another validator's result) without
+    /// going through an actual validation pass.
+    #[must_use]
+    pub fn new(valid_up_to: usize, error_len: Option<u8>) -> Self {
+        Self { valid_up_to, error_len, error_byte: None }
+    }
+
+    /// Returns the index in the original buffer up to which valid UTF-8
+    /// was verified. Mirrors `core::str::Utf8Error::valid_up_to`.
+    #[must_use]
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// Returns the length of the invalid byte sequence, or `None` if the
+    /// buffer ended before a full sequence could be read. Mirrors
+    /// `core::str::Utf8Error::error_len`.
+    #[must_use]
+    pub fn error_len(&self) -> Option<u8> {
+        self.error_len
+    }
+
+    /// Returns the specific byte value that violated the UTF-8 grammar,
+    /// or `None` for a truncated trailing sequence. See the field docs on
+    /// [`Utf8Error::error_byte`] for exactly which byte is reported.
+    #[must_use]
+    pub fn error_byte(&self) -> Option<u8> {
+        self.error_byte
+    }
+
+    /// True if the error is due to the buffer ending mid-sequence, i.e.
+    /// more bytes could turn `buf[valid_up_to..]` into valid UTF-8.
+    #[must_use]
+    pub fn is_incomplete(&self) -> bool {
+        self.error_len.is_none()
+    }
+
+    /// True if the bytes at `valid_up_to` can never be valid UTF-8, no
+    /// matter how many more bytes follow. The inverse of
+    /// [`is_incomplete`](Self::is_incomplete).
+    #[must_use]
+    pub fn is_invalid(&self) -> bool {
+        self.error_len.is_some()
+    }
+}
+
+impl core::fmt::Display for Utf8Error {
+    /// Matches `core::str::Utf8Error`'s message wording exactly, so
+    /// callers that already print/log a `str::from_utf8` error see the
+    /// same text switching to this crate's validator.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.error_len {
+            Some(len) => {
+                write!(f, "invalid utf-8 sequence of {len} bytes from index {}", self.valid_up_to)
+            }
+            None => write!(f, "incomplete utf-8 byte sequence from index {}", self.valid_up_to),
+        }
+    }
+}
+
+impl core::error::Error for Utf8Error {}
+
+impl From<core::str::Utf8Error> for Utf8Error {
+    /// Maps `valid_up_to()`/`error_len()` across from std's error type,
+    /// for callers that mix this crate with `core::str::from_utf8` and
+    /// want to unify on one error type. [`Utf8Error::error_byte`] is left
+    /// unset, since `core::str::Utf8Error` has no equivalent.
+    fn from(err: core::str::Utf8Error) -> Self {
+        Self::new(err.valid_up_to(), err.error_len().map(|len| len as u8))
+    }
+}
+
+/// An enriched, diagnostics-friendly view of a [`Utf8Error`], carrying a
+/// copy of the offending bytes so callers don't have to re-index the
+/// original buffer at the log site.
+///
+/// Kept separate from [`Utf8Error`] itself so the hot validation path can
+/// stay lean; construct one on demand via [`describe_error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorDetail {
+    pub valid_up_to: usize,
+    pub error_len: Option<u8>,
+    bad_bytes: [u8; 4],
+    bad_len: u8,
+}
+
+impl ErrorDetail {
+    /// The offending bytes starting at `valid_up_to`, up to 4 of them.
+    pub fn bad_bytes(&self) -> &[u8] {
+        &self.bad_bytes[..self.bad_len as usize]
+    }
+}
+
+/// Builds an [`ErrorDetail`] for `err`, copying up to 4 bytes from `buf`
+/// starting at `err.valid_up_to` for inline display in logs.
+///
+/// `buf` must be the same buffer (or at least share the same prefix) that
+/// produced `err`, otherwise the copied bytes are meaningless.
+pub fn describe_error(buf: &[u8], err: &Utf8Error) -> ErrorDetail {
+    let tail = &buf[err.valid_up_to.min(buf.len())..];
+    let bad_len = tail.len().min(4);
+    let mut bad_bytes = [0u8; 4];
+    bad_bytes[..bad_len].copy_from_slice(&tail[..bad_len]);
+    ErrorDetail {
+        valid_up_to: err.valid_up_to,
+        error_len: err.error_len,
+        bad_bytes,
+        bad_len: bad_len as u8,
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct Statistics {
     pub success_blocks_8x: usize,
     pub failed_blocks_8x: usize,
+    pub success_blocks_4x: usize,
+    pub failed_blocks_4x: usize,
     pub success_blocks_2x: usize,
     pub failed_blocks_2x: usize,
     pub unaligned_blocks: usize,
@@ -31,6 +176,15 @@ impl Statistics {
         self.success_blocks_8x as f64 / total as f64
     }
 
+    pub fn success_ratio_4x(&self) -> f64 {
+        let total = self.success_blocks_4x + self.failed_blocks_4x;
+        if total == 0 {
+            return 0.0;
+        }
+
+        self.success_blocks_4x as f64 / total as f64
+    }
+
     pub fn success_ratio_2x(&self) -> f64 {
         let total = self.success_blocks_2x + self.failed_blocks_2x;
         if total == 0 {
@@ -49,13 +203,468 @@ impl Statistics {
             total_8x as f64 / total_2x as f64
         }
     }
+
+    /// Field-wise adds `other`'s counters into `self`, so per-chunk
+    /// statistics (e.g. from [`validate_utf8_parallel`] or a streaming
+    /// loop over many small buffers) can be accumulated into one running
+    /// total.
+    pub fn merge(&mut self, other: &Statistics) {
+        self.success_blocks_8x += other.success_blocks_8x;
+        self.failed_blocks_8x += other.failed_blocks_8x;
+        self.success_blocks_4x += other.success_blocks_4x;
+        self.failed_blocks_4x += other.failed_blocks_4x;
+        self.success_blocks_2x += other.success_blocks_2x;
+        self.failed_blocks_2x += other.failed_blocks_2x;
+        self.unaligned_blocks += other.unaligned_blocks;
+        self.bytewise_checks += other.bytewise_checks;
+        self.non_ascii_checks += other.non_ascii_checks;
+        self.optimistic_2x_to_8x += other.optimistic_2x_to_8x;
+    }
+
+    /// Zeroes every counter in place, without reallocating (there's
+    /// nothing to reallocate — `Statistics` is all `usize` counters — but
+    /// this reads better at a call site than `*stats = Statistics::default()`).
+    pub fn reset(&mut self) {
+        *self = Statistics::default();
+    }
+
+    /// The number of bytes that went through a successful vectorized
+    /// (8x, 4x, or 2x word) block, i.e. never touched the bytewise
+    /// fallback.
+    pub fn bytes_scanned_fast(&self) -> usize {
+        (self.success_blocks_8x * 8 + self.success_blocks_4x * 4 + self.success_blocks_2x * 2) * word_bytes()
+    }
+
+    /// The number of bytes checked one at a time, outside a vectorized
+    /// block, named to pair with [`bytes_scanned_fast`](Self::bytes_scanned_fast).
+    /// This is [`bytewise_checks`](Self::bytewise_checks) unchanged: a byte
+    /// count where the validator counts one per byte
+    /// (`validate_utf8_std_with_stats`), but [`validate_utf8_with_stats`]
+    /// counts one per entry into its byte-wise fallback, not one per byte.
+    pub fn bytes_scanned_bytewise(&self) -> usize {
+        self.bytewise_checks
+    }
+
+    /// The column names for [`to_csv_row`](Self::to_csv_row), in the same
+    /// stable order, so tooling can diff runs across crate versions.
+    #[cfg(feature = "std")]
+    pub fn csv_header() -> &'static str {
+        "success_blocks_8x,failed_blocks_8x,success_blocks_4x,failed_blocks_4x,\
+         success_blocks_2x,failed_blocks_2x,unaligned_blocks,bytewise_checks,\
+         non_ascii_checks,optimistic_2x_to_8x"
+    }
+
+    /// Renders these statistics as a single CSV row, in the column order
+    /// given by [`csv_header`](Self::csv_header).
+    #[cfg(feature = "std")]
+    pub fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{}",
+            self.success_blocks_8x,
+            self.failed_blocks_8x,
+            self.success_blocks_4x,
+            self.failed_blocks_4x,
+            self.success_blocks_2x,
+            self.failed_blocks_2x,
+            self.unaligned_blocks,
+            self.bytewise_checks,
+            self.non_ascii_checks,
+            self.optimistic_2x_to_8x,
+        )
+    }
+}
+
+/// A [`Statistics`] with every counter behind an [`AtomicUsize`](core::sync::atomic::AtomicUsize),
+/// for accumulating totals across threads without a per-thread
+/// `Statistics` and a merge step. Pair with
+/// [`validate_utf8_with_atomic_stats`], which increments these counters
+/// with `Relaxed` ordering.
+#[derive(Debug, Default)]
+pub struct AtomicStatistics {
+    pub success_blocks_8x: core::sync::atomic::AtomicUsize,
+    pub failed_blocks_8x: core::sync::atomic::AtomicUsize,
+    pub success_blocks_4x: core::sync::atomic::AtomicUsize,
+    pub failed_blocks_4x: core::sync::atomic::AtomicUsize,
+    pub success_blocks_2x: core::sync::atomic::AtomicUsize,
+    pub failed_blocks_2x: core::sync::atomic::AtomicUsize,
+    pub unaligned_blocks: core::sync::atomic::AtomicUsize,
+    pub bytewise_checks: core::sync::atomic::AtomicUsize,
+    pub non_ascii_checks: core::sync::atomic::AtomicUsize,
+    pub optimistic_2x_to_8x: core::sync::atomic::AtomicUsize,
+}
+
+impl AtomicStatistics {
+    /// Field-wise adds `other`'s counters into `self` with `Relaxed`
+    /// ordering, mirroring [`Statistics::merge`].
+    pub fn merge(&self, other: &Statistics) {
+        use core::sync::atomic::Ordering::Relaxed;
+
+        self.success_blocks_8x.fetch_add(other.success_blocks_8x, Relaxed);
+        self.failed_blocks_8x.fetch_add(other.failed_blocks_8x, Relaxed);
+        self.success_blocks_4x.fetch_add(other.success_blocks_4x, Relaxed);
+        self.failed_blocks_4x.fetch_add(other.failed_blocks_4x, Relaxed);
+        self.success_blocks_2x.fetch_add(other.success_blocks_2x, Relaxed);
+        self.failed_blocks_2x.fetch_add(other.failed_blocks_2x, Relaxed);
+        self.unaligned_blocks.fetch_add(other.unaligned_blocks, Relaxed);
+        self.bytewise_checks.fetch_add(other.bytewise_checks, Relaxed);
+        self.non_ascii_checks.fetch_add(other.non_ascii_checks, Relaxed);
+        self.optimistic_2x_to_8x.fetch_add(other.optimistic_2x_to_8x, Relaxed);
+    }
+
+    /// Loads every counter with `Relaxed` ordering into a plain
+    /// [`Statistics`] snapshot.
+    #[must_use]
+    pub fn snapshot(&self) -> Statistics {
+        use core::sync::atomic::Ordering::Relaxed;
+
+        Statistics {
+            success_blocks_8x: self.success_blocks_8x.load(Relaxed),
+            failed_blocks_8x: self.failed_blocks_8x.load(Relaxed),
+            success_blocks_4x: self.success_blocks_4x.load(Relaxed),
+            failed_blocks_4x: self.failed_blocks_4x.load(Relaxed),
+            success_blocks_2x: self.success_blocks_2x.load(Relaxed),
+            failed_blocks_2x: self.failed_blocks_2x.load(Relaxed),
+            unaligned_blocks: self.unaligned_blocks.load(Relaxed),
+            bytewise_checks: self.bytewise_checks.load(Relaxed),
+            non_ascii_checks: self.non_ascii_checks.load(Relaxed),
+            optimistic_2x_to_8x: self.optimistic_2x_to_8x.load(Relaxed),
+        }
+    }
+}
+
+/// Like [`validate_utf8_with_stats`], but accumulates into a shared
+/// [`AtomicStatistics`] instead of a per-call [`Statistics`], so multiple
+/// threads validating different chunks of a buffer can feed the same
+/// sink without a merge step. Internally still runs the scan against a
+/// local [`Statistics`] and folds it into `stats` in one batch of
+/// `Relaxed` adds, rather than making every counter increment atomic.
+pub fn validate_utf8_with_atomic_stats(buf: &[u8], stats: &AtomicStatistics) -> Result<(), Utf8Error> {
+    let mut local = Statistics::default();
+    let result = validate_utf8_with_stats(buf, Some(&mut local));
+    stats.merge(&local);
+    result
+}
+
+/// Below this length, the alignment and word-block setup [`validate_utf8`]
+/// otherwise performs costs more than just checking each byte, so
+/// [`validate_utf8`] dispatches straight to [`validate_utf8_small`]
+/// instead. See the short-string workload in `benches/vs_std.rs`.
+const SMALL_INPUT_THRESHOLD: usize = 2 * WORD_BYTES;
+
+/// Validates `buf` as UTF-8 via a pure bytewise scan, without ever
+/// computing an `align_offset` or a word-block bound.
+///
+/// [`validate_utf8`] dispatches here directly for inputs shorter than
+/// `SMALL_INPUT_THRESHOLD`, where that setup dominates the cost of just
+/// checking each byte in turn.
+pub fn validate_utf8_small(buf: &[u8]) -> Result<(), Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+
+    while curr < end {
+        if buf[curr] < 128 {
+            curr += 1;
+            continue;
+        }
+
+        match validate_non_acii_bytes(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates `buf` as UTF-8 in a `const` context, e.g. for asserting a
+/// string literal embedded as a `&[u8]` constant is valid UTF-8 at
+/// compile time.
+///
+/// This is a pure bytewise scan identical in shape to
+/// [`validate_utf8_small`] — `validate_non_acii_bytes` is already a
+/// `const fn`, so the only change needed here is dropping the
+/// pointer-based alignment and word-block machinery [`validate_utf8`]
+/// uses at runtime, since raw pointer reads aren't `const`-callable.
+/// It gives identical results to [`validate_utf8`] on every input, just
+/// without that machinery's throughput.
+///
+/// # Examples
+///
+/// ```
+/// use fast_utf8::validate_utf8_const;
+///
+/// const _: () = assert!(validate_utf8_const(b"hello").is_ok());
+/// const _: () = assert!(validate_utf8_const(b"\xFF").is_err());
+/// ```
+pub const fn validate_utf8_const(buf: &[u8]) -> Result<(), Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+
+    while curr < end {
+        if buf[curr] < 128 {
+            curr += 1;
+            continue;
+        }
+
+        match validate_non_acii_bytes(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
 }
 
 #[inline(never)]
 pub fn validate_utf8(buf: &[u8]) -> Result<(), Utf8Error> {
+    if buf.len() < SMALL_INPUT_THRESHOLD {
+        return validate_utf8_small(buf);
+    }
+
+    #[cfg(all(feature = "simd", feature = "std", target_arch = "x86_64"))]
+    {
+        if !scalar_backend_forced() && is_x86_feature_detected!("avx2") {
+            return validate_utf8_avx2(buf);
+        }
+    }
+
+    #[cfg(all(feature = "simd", feature = "std", target_arch = "aarch64"))]
+    {
+        if !scalar_backend_forced() && is_aarch64_feature_detected!("neon") {
+            return validate_utf8_neon(buf);
+        }
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))]
+    {
+        if !scalar_backend_forced() {
+            return validate_utf8_simd128(buf);
+        }
+    }
+
+    validate_utf8_with_stats(buf, None)
+}
+
+/// Validates `len` bytes starting at `ptr`, exactly like [`validate_utf8`],
+/// for callers holding a raw pointer instead of a safe slice — e.g. an
+/// FFI boundary, or a memory-mapped file region borrowed for the
+/// duration of the call.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of `len` bytes for the duration of this
+/// call, and that memory must not be mutated concurrently (the usual
+/// [`std::slice::from_raw_parts`] requirements).
+pub unsafe fn validate_utf8_raw(ptr: *const u8, len: usize) -> Result<(), Utf8Error> {
+    // SAFETY: forwarded to the caller of `validate_utf8_raw`
+    let buf = unsafe { core::slice::from_raw_parts(ptr, len) };
+    validate_utf8(buf)
+}
+
+/// Validates `buf` exactly like [`validate_utf8`], but reports
+/// [`Utf8Error::valid_up_to`] relative to `base` instead of `buf`'s own
+/// start.
+///
+/// Useful when `buf` is a window into a larger parent buffer (e.g. one
+/// chunk of a file being validated piecewise) and the caller wants
+/// `valid_up_to` expressed in the parent's coordinate space, rather than
+/// having to add `base` back in themselves at every call site.
+pub fn validate_utf8_with_offset(buf: &[u8], base: usize) -> Result<(), Utf8Error> {
+    validate_utf8(buf).map_err(|e| Utf8Error { valid_up_to: e.valid_up_to + base, ..e })
+}
+
+/// Validates `buf` as UTF-8, additionally reporting where real content
+/// begins if `buf` opens with a UTF-8 byte order mark (`EF BB BF`).
+///
+/// Returns `Ok(3)` for a buffer that starts with the BOM (whether or not
+/// anything follows it), `Ok(0)` for one that doesn't, and `Err` if any
+/// part of `buf` — including a BOM appearing after the start, which is
+/// just three ordinary characters at that point and not stripped — fails
+/// to validate.
+pub fn validate_utf8_skip_bom(buf: &[u8]) -> Result<usize, Utf8Error> {
+    validate_utf8(buf)?;
+
+    if buf.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        Ok(3)
+    } else {
+        Ok(0)
+    }
+}
+
+/// Validates at most `max_bytes` of `buf`, backing up to the nearest
+/// character boundary at or before that budget so a multi-byte character
+/// straddling it is never split, and returns how many bytes actually got
+/// validated. Useful for rate-limited or sandboxed parsing that wants to
+/// cap how much of a buffer it processes without risking a dangling
+/// partial character at the cut point.
+///
+/// The budget only ever constrains *where the scan stops*; it doesn't
+/// change what counts as an error. If `buf` itself is invalid before the
+/// (possibly backed-up) budget is reached, this still returns `Err` for
+/// that error, same as [`validate_utf8`] would.
+pub fn validate_utf8_bounded(buf: &[u8], max_bytes: usize) -> Result<usize, Utf8Error> {
+    let mut boundary = max_bytes.min(buf.len());
+
+    // walk back to the nearest byte that isn't a continuation byte, so a
+    // multi-byte character straddling the budget is validated as a whole
+    // or not at all, never split
+    while boundary > 0 && boundary < buf.len() && matches!(buf[boundary], 0x80..=0xBF) {
+        boundary -= 1;
+    }
+
+    validate_utf8(&buf[..boundary])?;
+    Ok(boundary)
+}
+
+/// Validates `buf` exactly like [`validate_utf8`], with an explicitly
+/// specified and tested `error_len` contract for every kind of malformed
+/// sequence this crate can report:
+///
+/// - `error_len: Some(1)` — a stray continuation byte, an invalid lead
+///   byte (`0xC0`, `0xC1`, `0xF5..=0xFF`), or a lead byte whose very next
+///   byte already breaks the grammar (wrong tag bits, or a range
+///   violation such as an overlong encoding, a surrogate half, or a
+///   scalar above `U+10FFFF`) — the error is pinned to that first bad
+///   byte because nothing past it was consumed.
+/// - `error_len: Some(2)` — a 3- or 4-byte lead followed by one good
+///   continuation byte and then a byte that breaks the grammar.
+/// - `error_len: Some(3)` — a 4-byte lead followed by two good
+///   continuation bytes and then a byte that breaks the grammar.
+/// - `error_len: None` — the buffer ends before the sequence starting at
+///   `valid_up_to` is complete, even though every byte seen so far is a
+///   legal prefix of some valid sequence.
+///
+/// This is the same validator as [`validate_utf8`] under a name that
+/// documents the contract explicitly for callers who match on
+/// `error_len` and want that behavior spelled out rather than inferred
+/// from std's `core::str::Utf8Error` docs by analogy. See the
+/// `error_len_*` tests for a curated corpus exercising every value above
+/// against known-malformed sequences.
+pub fn validate_utf8_strict(buf: &[u8]) -> Result<(), Utf8Error> {
+    validate_utf8(buf)
+}
+
+/// A validation backend [`choose_backend`] can route a buffer to, from
+/// lowest to highest setup cost.
+///
+/// Only lists backends this crate actually implements (see
+/// `validate_utf8_avx2`); there is no dedicated SSE2 kernel, so buffers
+/// too small to amortize AVX2's setup cost are routed to [`Backend::Scalar`]
+/// rather than a nonexistent intermediate tier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Scalar,
+    Avx2,
+}
+
+/// Below this many bytes, [`choose_backend`] always picks
+/// [`Backend::Scalar`]: AVX2's lane setup and misaligned-tail handling
+/// aren't paid back by buffers this short. Tuned against the bundled
+/// asset spectrum (27B to 1.5MB) used by the `vs_std` bench.
+pub const AVX2_THRESHOLD: usize = 8192;
+
+/// Picks the backend [`validate_utf8_auto`] should use for a `len`-byte
+/// buffer, given whether AVX2 was detected available at runtime.
+pub const fn choose_backend(len: usize, avx2_available: bool) -> Backend {
+    if avx2_available && len >= AVX2_THRESHOLD {
+        Backend::Avx2
+    } else {
+        Backend::Scalar
+    }
+}
+
+/// Like [`validate_utf8`], but routes through the explicit, inspectable
+/// [`choose_backend`] size cutoff instead of always preferring the
+/// fastest detected ISA regardless of buffer length.
+#[cfg(all(feature = "simd", feature = "std", target_arch = "x86_64"))]
+pub fn validate_utf8_auto(buf: &[u8]) -> Result<(), Utf8Error> {
+    let avx2_available = !scalar_backend_forced() && is_x86_feature_detected!("avx2");
+    match choose_backend(buf.len(), avx2_available) {
+        Backend::Avx2 => validate_utf8_avx2(buf),
+        Backend::Scalar => validate_utf8_with_stats(buf, None),
+    }
+}
+
+/// [`choose_backend`] never selects [`Backend::Avx2`] without the `simd`
+/// feature or off `x86_64`, so this falls straight back to
+/// [`validate_utf8_with_stats`].
+#[cfg(not(all(feature = "simd", feature = "std", target_arch = "x86_64")))]
+pub fn validate_utf8_auto(buf: &[u8]) -> Result<(), Utf8Error> {
     validate_utf8_with_stats(buf, None)
 }
 
+/// Distance, in bytes, ahead of the current 8-word block scan position at
+/// which [`prefetch_block_ahead`] issues its hint — a few cache lines,
+/// enough for the hardware to have the data ready by the time the loop
+/// reaches it without prefetching so far ahead the line is evicted again
+/// first.
+///
+/// Distinct from [`PREFETCH_DISTANCE`], which tunes
+/// [`validate_utf8_stream_large`]'s coarser, buffer-wide prefetch pass
+/// instead of this crate's 8-word block loop.
+#[cfg(feature = "prefetch")]
+const BLOCK_PREFETCH_DISTANCE: usize = 4 * 8 * WORD_BYTES;
+
+/// Issues a software prefetch hint for the cache line at
+/// `start + curr + BLOCK_PREFETCH_DISTANCE`, if that offset still falls
+/// within `end`. A no-op on targets this crate doesn't have a prefetch
+/// intrinsic wired up for.
+///
+/// Purely a throughput hint: skipping or mistiming it can never change
+/// the bytes [`validate_utf8_with_stats`] reads or the result it
+/// produces, only how quickly it gets there.
+#[cfg(feature = "prefetch")]
+#[inline(always)]
+fn prefetch_block_ahead(start: *const u8, curr: usize, end: usize) {
+    let target = curr + BLOCK_PREFETCH_DISTANCE;
+    if target >= end {
+        return;
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    // SAFETY: `_mm_prefetch` never dereferences the pointer it's given;
+    // it merely hints the CPU to fetch the cache line containing it
+    unsafe {
+        use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+        _mm_prefetch(start.add(target).cast::<i8>(), _MM_HINT_T0);
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    // SAFETY: `prfm` never dereferences its operand either, for the same
+    // reason as the x86_64 case above
+    unsafe {
+        core::arch::asm!("prfm pldl1keep, [{0}]", in(reg) start.add(target));
+    }
+}
+
+/// Pure bytewise scan with no word-block fast path at all, used by
+/// [`validate_utf8_with_stats`] and [`validate_utf8_blocked`] as the
+/// fallback for the (never actually reachable for a `u8` pointer, see
+/// their own doc comments) case where `align_offset` comes back
+/// `usize::MAX`.
+fn validate_utf8_bytewise_with_stats(
+    buf: &[u8],
+    mut stats: Option<&mut Statistics>,
+) -> Result<(), Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+
+    while curr < end {
+        if buf[curr] < 128 {
+            curr += 1;
+            continue;
+        }
+
+        if let Some(stats) = stats.as_mut() {
+            stats.non_ascii_checks += 1;
+        }
+
+        match validate_non_ascii_run_swar(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
 #[inline(always)]
 pub fn validate_utf8_with_stats(
     buf: &[u8],
@@ -67,12 +676,22 @@ pub fn validate_utf8_with_stats(
     // calculate the maximum byte at which a block of size N could begin,
     // without taking alignment into account
     let block_end_2x = block_end(end, 2 * WORD_BYTES);
+    let block_end_4x = block_end(end, 4 * WORD_BYTES);
     let block_end_8x = block_end(end, 8 * WORD_BYTES);
 
     // calculate the byte offset until the first word aligned block
     let align_offset = start.align_offset(WORD_BYTES);
 
-    'outer: while curr < end {
+    // `align_offset` should only ever return `usize::MAX` for ZST
+    // pointers, which a `u8` pointer can never be — but outside `std` the
+    // compiler seems unable to determine that on its own. Checking it
+    // once here and handing off entirely to a plain bytewise scan avoids
+    // paying for the check again on every outer-loop iteration below.
+    if align_offset == usize::MAX {
+        return validate_utf8_bytewise_with_stats(buf, stats);
+    }
+
+    while curr < end {
         // this block allows us to inexpensively jump to the non-ASCII branch
         // without having to go through the outer loop condition again
         'ascii: {
@@ -80,15 +699,6 @@ pub fn validate_utf8_with_stats(
                 break 'ascii;
             }
 
-            // `align_offset` should only ever return `usize::MAX` for ZST
-            // pointers to, so ideally the check/branch should be optimized out
-            // NOTE: outside of the `std` library, the compiler seems to be
-            // unable to determine this must always be false
-            if align_offset == usize::MAX {
-                curr += 1;
-                continue 'outer;
-            }
-
             // check if `curr`'s pointer is word-aligned, otherwise advance curr
             // bytewise until it is byte aligned
             let offset = align_offset.wrapping_sub(curr) % WORD_BYTES;
@@ -117,17 +727,31 @@ pub fn validate_utf8_with_stats(
                 }
             }
 
+            // holds the masked word(s) of whichever block failed below, so
+            // the position search doesn't have to re-read memory or
+            // re-mask the words the block loop already examined
+            let mut dirty = [0usize; 8];
+
             // check 8 or 2 word sized blocks for non-ASCII bytes
             let non_ascii = 'block: {
                 while curr < block_end_8x {
+                    #[cfg(feature = "prefetch")]
+                    prefetch_block_ahead(start, curr, end);
+
+                    // guards the invariant `block_end_8x` is meant to
+                    // establish, so a future refactor that loosens the
+                    // loop condition trips this instead of silently
+                    // reading past `buf`'s end
+                    debug_assert!(curr + 8 * WORD_BYTES <= end, "8-word block would read past buffer end");
                     // SAFETY: the loop condition guarantees that there is
                     // sufficient room for N word-blocks in the buffer
-                    let block = unsafe { &*(start.add(curr) as *const [usize; 8]) };
-                    if has_non_ascii_byte(block) {
+                    let block: [usize; 8] = unsafe { ByteVector::load(start.add(curr)) };
+                    if let Some(masked) = mask_if_non_ascii(block) {
                         if let Some(stats) = stats.as_mut() {
                             stats.failed_blocks_8x += 1;
                         }
 
+                        dirty = masked;
                         break 'block 8;
                     }
 
@@ -138,18 +762,57 @@ pub fn validate_utf8_with_stats(
                     }
                 }
 
+                // check a single 4-word block for non-ASCII bytes, as an
+                // intermediate step between the 8x and 2x loops: once the
+                // 8x loop can't fit another whole 8-word block, there's
+                // often still room for one 4-word block before the 2x
+                // loop's tighter granularity is needed, and taking it
+                // reduces the branch mispredicts at the 8x-to-2x
+                // transition on inputs sized just under a multiple of 8
+                // words.
+                if curr < block_end_4x {
+                    // guards the invariant `block_end_4x` is meant to
+                    // establish, so a future refactor that loosens the
+                    // condition trips this instead of silently reading
+                    // past `buf`'s end
+                    debug_assert!(curr + 4 * WORD_BYTES <= end, "4-word block would read past buffer end");
+                    // SAFETY: the condition above guarantees that there is
+                    // sufficient room for a 4-word block in the buffer
+                    let block: [usize; 4] = unsafe { ByteVector::load(start.add(curr)) };
+                    if let Some(masked) = mask_if_non_ascii(block) {
+                        if let Some(stats) = stats.as_mut() {
+                            stats.failed_blocks_4x += 1;
+                        }
+
+                        dirty[..4].copy_from_slice(&masked);
+                        break 'block 4;
+                    }
+
+                    curr += 4 * WORD_BYTES;
+
+                    if let Some(stats) = stats.as_mut() {
+                        stats.success_blocks_4x += 1;
+                    }
+                }
+
                 // check 2-word sized blocks for non-ASCII bytes
                 // word-alignment has been determined at this point, so only
                 // the buffer length needs to be taken into consideration
                 while curr < block_end_2x {
+                    // guards the invariant `block_end_2x` is meant to
+                    // establish, so a future refactor that loosens the
+                    // loop condition trips this instead of silently
+                    // reading past `buf`'s end
+                    debug_assert!(curr + 2 * WORD_BYTES <= end, "2-word block would read past buffer end");
                     // SAFETY: the loop condition guarantees that there is
                     // sufficient room for N word-blocks in the buffer
-                    let block = unsafe { &*(start.add(curr) as *const [usize; 2]) };
-                    if has_non_ascii_byte(&block) {
+                    let block: [usize; 2] = unsafe { ByteVector::load(start.add(curr)) };
+                    if let Some(masked) = mask_if_non_ascii(block) {
                         if let Some(stats) = stats.as_mut() {
                             stats.failed_blocks_2x += 1;
                         }
 
+                        dirty[..2].copy_from_slice(&masked);
                         break 'block 2;
                     }
 
@@ -163,23 +826,14 @@ pub fn validate_utf8_with_stats(
                 break 'block 0;
             };
 
-            // if the block loop was stopped due to a non-ascii byte
-            // in some word, do another word-wise search using the same word
-            // buffer used before in order to avoid having to checking all
-            // bytes individually again.
+            // if the block loop was stopped due to a non-ascii byte in some
+            // word, reuse the already-masked words captured in `dirty`
+            // instead of re-reading and re-masking memory to find the
+            // exact offset.
             if non_ascii > 0 {
-                // calculate the amount of bytes that can be skipped without
-                // having to check them individually
-                // SAFETY: the bound invariants as in the previous [8|2]-word
-                // block loop apply, since `curr` has not been changed since
-                curr += unsafe {
-                    let ptr = start.add(curr);
-                    let block = slice::from_raw_parts(ptr as *const usize, non_ascii);
-                    // SAFETY: since a previous [8|2]-word check "failed",
-                    // there *must* be at least one non-ASCII byte somewhere in
-                    // the block
-                    non_ascii_byte_position(&block) as usize
-                };
+                // SAFETY: since a previous [8|4|2]-word check "failed", there
+                // *must* be at least one non-ASCII byte somewhere in `dirty`
+                curr += unsafe { non_ascii_byte_position(&dirty[..non_ascii]) as usize };
 
                 break 'ascii;
             }
@@ -188,10 +842,12 @@ pub fn validate_utf8_with_stats(
                 stats.bytewise_checks += 1;
             }
 
-            // ...otherwise, fall back to byte-wise checks
+            // ...otherwise, fall back to byte-wise checks. `curr` itself
+            // has not been checked yet at this point whenever the block
+            // loop above consumed at least one whole block (it only knows
+            // the *block* was all-ASCII, not that the byte right after it
+            // is), so it must be examined before advancing past it.
             loop {
-                curr += 1;
-
                 // curr may already have been at end after exhaustive block loop
                 if curr >= end {
                     return Ok(());
@@ -200,6 +856,8 @@ pub fn validate_utf8_with_stats(
                 if buf[curr] >= 128 {
                     break 'ascii;
                 }
+
+                curr += 1;
             }
         }
 
@@ -209,7 +867,7 @@ pub fn validate_utf8_with_stats(
             stats.non_ascii_checks += 1;
         }
 
-        match validate_non_acii_bytes(buf, curr, end) {
+        match validate_non_ascii_run_swar(buf, curr, end) {
             Ok(next) => curr = next,
             Err(e) => return Err(e),
         }
@@ -218,65 +876,242 @@ pub fn validate_utf8_with_stats(
     Ok(())
 }
 
-/// Returns `true` if any byte in `block` contains a non-ASCII byte.
-///
-/// # Note
-///
-/// This function is written to allow for relatively reliable
-/// auto-vectorization, not code size.
+/// Single-block-size scan loop shared by [`validate_utf8_ascii_biased`] and
+/// [`validate_utf8_unicode_biased`]: the same alignment handling and
+/// bytewise fallback as [`validate_utf8_with_stats`], but committing to one
+/// block width `N` (in words) up front instead of cascading from 8-word
+/// blocks down to 2-word blocks on a failure.
 #[inline(always)]
-const fn has_non_ascii_byte<const N: usize>(block: &[usize; N]) -> bool {
-    // mask each word in the block
-    let vector = mask_block(block);
+fn validate_utf8_blocked<const N: usize>(buf: &[u8]) -> Result<(), Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+    let start = buf.as_ptr();
 
-    let mut i = 0;
-    let mut res = 0;
-    while i < N {
-        res |= vector[i];
-        i += 1;
+    let block_end_n = block_end(end, N * WORD_BYTES);
+    let align_offset = start.align_offset(WORD_BYTES);
+
+    // see the matching check in `validate_utf8_with_stats`
+    if align_offset == usize::MAX {
+        return validate_utf8_bytewise_with_stats(buf, None);
     }
 
-    res > 0
-}
+    while curr < end {
+        'ascii: {
+            if buf[curr] >= 128 {
+                break 'ascii;
+            }
 
-/// Masks every byte of every word in `block`, so that only the MSB of each byte
-/// remains, indicating a non-ASCII byte.
-#[inline(always)]
-const fn mask_block<const N: usize>(block: &[usize; N]) -> [usize; N] {
-    let mut masked = [0usize; N];
-    let mut i = 0;
+            let offset = align_offset.wrapping_sub(curr) % WORD_BYTES;
+            if offset > 0 {
+                let aligned = curr + offset;
+                loop {
+                    curr += 1;
 
-    while i < N {
-        masked[i] = block[i] & NONASCII_MASK;
-        i += 1;
-    }
+                    if curr == end {
+                        return Ok(());
+                    }
 
-    masked
-}
+                    if buf[curr] >= 128 {
+                        break 'ascii;
+                    }
 
-/// Determines the precise position of the first non-ASCII byte in the given
-/// `block`.
-///
+                    if curr == aligned {
+                        break;
+                    }
+                }
+            }
+
+            while curr < block_end_n {
+                debug_assert!(curr + N * WORD_BYTES <= end, "block would read past buffer end");
+                // SAFETY: the loop condition guarantees that there is
+                // sufficient room for an N-word block in the buffer
+                let block: [usize; N] = unsafe { ByteVector::load(start.add(curr)) };
+                if let Some(masked) = mask_if_non_ascii(block) {
+                    // SAFETY: `mask_if_non_ascii` only returns `Some` when a
+                    // non-ASCII byte is actually present somewhere in it
+                    curr += unsafe { non_ascii_byte_position(&masked) as usize };
+                    break 'ascii;
+                }
+
+                curr += N * WORD_BYTES;
+            }
+
+            loop {
+                if curr >= end {
+                    return Ok(());
+                }
+
+                if buf[curr] >= 128 {
+                    break 'ascii;
+                }
+
+                curr += 1;
+            }
+        }
+
+        match validate_non_ascii_run_swar(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates `buf`, biased toward the common case where it's almost
+/// entirely ASCII (log lines, source code, most web content). Commits to
+/// 8-word blocks up front, instead of the 8-then-4-then-2-word cascade
+/// [`validate_utf8_with_stats`] falls back through on a block failure,
+/// since a non-ASCII byte is expected to be rare enough that paying for
+/// the smaller-block fallback tier isn't worth it. This formalizes the
+/// same "start optimistic, only pay for finer-grained blocks if the
+/// coarse ones actually fail" idea `Statistics::optimistic_2x_to_8x`
+/// measures, as a static strategy instead of a runtime-adaptive one.
+///
+/// For a mix of ASCII and multi-byte content, or when in doubt, use
+/// [`validate_utf8`] instead.
+pub fn validate_utf8_ascii_biased(buf: &[u8]) -> Result<(), Utf8Error> {
+    if buf.len() < SMALL_INPUT_THRESHOLD {
+        return validate_utf8_small(buf);
+    }
+
+    validate_utf8_blocked::<8>(buf)
+}
+
+/// Validates `buf`, biased toward the case where non-ASCII bytes are dense
+/// (CJK, Cyrillic, or emoji-heavy text). Commits to single-word blocks up
+/// front, since wider 8-word or 2-word blocks would be likely to contain a
+/// non-ASCII byte anyway and the extra width would just be wasted work
+/// before falling back.
+///
+/// For a mix of ASCII and multi-byte content, or when in doubt, use
+/// [`validate_utf8`] instead.
+pub fn validate_utf8_unicode_biased(buf: &[u8]) -> Result<(), Utf8Error> {
+    if buf.len() < SMALL_INPUT_THRESHOLD {
+        return validate_utf8_small(buf);
+    }
+
+    validate_utf8_blocked::<1>(buf)
+}
+
+/// Returns `true` if any byte in `block` contains a non-ASCII byte.
+///
+/// # Note
+///
+/// This function is written to allow for relatively reliable
+/// auto-vectorization, not code size. Under the `small-code` feature, a
+/// tight early-exit loop over the individual words is used instead — it
+/// can't be vectorized as reliably, but doesn't pull in the reduce-then-
+/// compare code the vectorization-friendly form generates for every `N`
+/// it's instantiated with. See `tests/small_code.rs` for the measured
+/// size delta this and the `#[inline(always)]` -> `#[inline]` changes
+/// below add up to.
+#[cfg(not(feature = "small-code"))]
+#[inline(always)]
+const fn has_non_ascii_byte<const N: usize>(block: &[usize; N]) -> bool {
+    // mask each word in the block
+    let vector = mask_block(block);
+
+    let mut i = 0;
+    let mut res = 0;
+    while i < N {
+        res |= vector[i];
+        i += 1;
+    }
+
+    res > 0
+}
+
+/// Returns `true` if any byte in `block` contains a non-ASCII byte.
+///
+/// `small-code` counterpart of the default `has_non_ascii_byte`: a plain
+/// loop that returns as soon as it finds a non-ASCII word, instead of
+/// masking and reducing the whole block unconditionally.
+#[cfg(feature = "small-code")]
+#[inline]
+const fn has_non_ascii_byte<const N: usize>(block: &[usize; N]) -> bool {
+    let mut i = 0;
+    while i < N {
+        if block[i] & NONASCII_MASK != 0 {
+            return true;
+        }
+        i += 1;
+    }
+
+    false
+}
+
+/// Masks every byte of every word in `block`, so that only the MSB of each byte
+/// remains, indicating a non-ASCII byte.
+#[cfg_attr(not(feature = "small-code"), inline(always))]
+#[cfg_attr(feature = "small-code", inline)]
+const fn mask_block<const N: usize>(block: &[usize; N]) -> [usize; N] {
+    let mut masked = [0usize; N];
+    let mut i = 0;
+
+    while i < N {
+        masked[i] = block[i] & NONASCII_MASK;
+        i += 1;
+    }
+
+    masked
+}
+
+/// Masks `block` and returns the masked words if any non-ASCII byte was
+/// found, or `None` if the whole block is ASCII.
+///
+/// Callers that need the exact offset of the offending byte on failure
+/// should hold onto the returned masked block and pass it to
+/// [`non_ascii_byte_position`], instead of re-reading and re-masking the
+/// same memory a second time.
+#[cfg_attr(not(feature = "small-code"), inline(always))]
+#[cfg_attr(feature = "small-code", inline)]
+fn mask_if_non_ascii<const N: usize>(block: [usize; N]) -> Option<[usize; N]> {
+    if block.any_high_bit() {
+        Some(mask_block(&block))
+    } else {
+        None
+    }
+}
+
+/// Byte index, in memory order, of the lowest-addressed set bit in `word`.
+///
+/// A word loaded from memory via a native-endianness read places the
+/// lowest-addressed byte in the least-significant bits on little-endian
+/// targets, but in the most-significant bits on big-endian ones — so
+/// `trailing_zeros` walks memory order on LE, while `leading_zeros` is the
+/// one that does on BE. `big_endian` is a parameter (rather than reading
+/// `cfg!` directly) purely so tests can exercise both branches on any
+/// host; the real call site always passes `cfg!(target_endian = "big")`.
+#[cfg_attr(not(feature = "small-code"), inline(always))]
+#[cfg_attr(feature = "small-code", inline)]
+const fn byte_index_in_word(word: usize, big_endian: bool) -> u32 {
+    if big_endian { word.leading_zeros() / u8::BITS } else { word.trailing_zeros() / u8::BITS }
+}
+
+/// Determines the precise position of the first non-ASCII byte, given a
+/// block that has already been masked (e.g. by [`mask_if_non_ascii`]).
+///
+/// Operating on an already-masked block means the caller doesn't have to
+/// re-read the same memory or re-apply [`NONASCII_MASK`] a second time on
+/// the failure path.
+///
 /// # Safety
 ///
-/// The caller has to guarantee, that `block` does in fact contain a non-ASCII
-/// byte.
+/// The caller has to guarantee, that `masked` does in fact contain a
+/// non-zero (i.e. originally non-ASCII) word.
 ///
 /// # Note
 ///
 /// It would be valid to just return 0 or panic, but this has non-trivial impact
 /// on generated code size.
-#[inline(always)]
+#[cfg_attr(not(feature = "small-code"), inline(always))]
+#[cfg_attr(feature = "small-code", inline)]
 #[cold]
-const unsafe fn non_ascii_byte_position(block: &[usize]) -> u32 {
+const unsafe fn non_ascii_byte_position(masked: &[usize]) -> u32 {
     let mut i = 0;
-    while i < block.len() {
-        // number of trailing zeroes in a word divided by the size of a word is
-        // equivalent to the number of valid ASCII bytes, since the first one
-        // bit will be MSB of the first byte within the word that is non-ASCII.
-        let ctz = (block[i] & NONASCII_MASK).trailing_zeros();
-        if ctz < usize::BITS {
-            let byte = ctz / WORD_BYTES as u32;
+    while i < masked.len() {
+        if masked[i] != 0 {
+            let byte = byte_index_in_word(masked[i], cfg!(target_endian = "big"));
             return byte + (i as u32 * WORD_BYTES as u32);
         }
 
@@ -291,6 +1126,232 @@ const unsafe fn non_ascii_byte_position(block: &[usize]) -> u32 {
     unsafe { hint::unreachable_unchecked() }
 }
 
+/// Caller-selectable word-block widths for [`validate_utf8_configured`],
+/// for hardware where the hardcoded 8x/4x/2x cascade [`validate_utf8`] uses
+/// isn't the best fit (e.g. wider vector units that would benefit from a
+/// bigger primary block).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf8Config {
+    pub primary_block_words: usize,
+    pub secondary_block_words: usize,
+}
+
+impl Utf8Config {
+    /// The primary (8-word) and secondary (2-word) widths
+    /// [`validate_utf8_with_stats`] itself hardcodes; its single 4-word
+    /// tier between the two has no counterpart in this config.
+    pub const DEFAULT: Utf8Config = Utf8Config { primary_block_words: 8, secondary_block_words: 2 };
+
+    /// # Panics
+    ///
+    /// Panics unless `primary_block_words >= secondary_block_words >= 1`.
+    fn validate(&self) {
+        assert!(
+            self.secondary_block_words >= 1 && self.primary_block_words >= self.secondary_block_words,
+            "Utf8Config requires primary_block_words >= secondary_block_words >= 1, got {self:?}"
+        );
+    }
+}
+
+impl Default for Utf8Config {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Scans whole `block_words`-word blocks starting at `curr`, stopping
+/// before `block_end`, for the first non-ASCII byte.
+///
+/// Returns `Ok(curr)` with `curr` advanced past every whole block that
+/// was entirely ASCII once `block_end` is reached, or `Err(pos)` with the
+/// exact byte index of the first non-ASCII byte found.
+fn scan_ascii_blocks(buf: &[u8], mut curr: usize, block_end: usize, block_words: usize) -> Result<usize, usize> {
+    let start = buf.as_ptr();
+    let block_bytes = block_words * WORD_BYTES;
+
+    while curr < block_end {
+        for i in 0..block_words {
+            // SAFETY: `curr < block_end` guarantees `block_words` whole
+            // words are readable starting at `curr`
+            let word = unsafe { (start.add(curr + i * WORD_BYTES) as *const usize).read_unaligned() };
+            if contains_nonascii(word) {
+                let masked = word & NONASCII_MASK;
+                // SAFETY: `contains_nonascii` guarantees a set high bit
+                let offset = unsafe { non_ascii_byte_position(&[masked]) } as usize;
+                return Err(curr + i * WORD_BYTES + offset);
+            }
+        }
+        curr += block_bytes;
+    }
+
+    Ok(curr)
+}
+
+/// Validates `buf` as UTF-8 using the same word-block-masking strategy as
+/// [`validate_utf8_with_stats`], but with block widths taken from `cfg`
+/// instead of the hardcoded 8x/2x cascade, so callers can tune the
+/// primary/secondary block size to their hardware without recompiling.
+///
+/// # Panics
+///
+/// Panics if `cfg.primary_block_words < cfg.secondary_block_words` or
+/// `cfg.secondary_block_words == 0` — see `Utf8Config::validate`.
+pub fn validate_utf8_configured(buf: &[u8], cfg: &Utf8Config) -> Result<(), Utf8Error> {
+    cfg.validate();
+
+    let (mut curr, end) = (0, buf.len());
+    let start = buf.as_ptr();
+    let align_offset = start.align_offset(WORD_BYTES);
+
+    let block_end_primary = block_end(end, cfg.primary_block_words * WORD_BYTES);
+    let block_end_secondary = block_end(end, cfg.secondary_block_words * WORD_BYTES);
+
+    while curr < end {
+        if buf[curr] >= 128 {
+            match validate_non_acii_bytes(buf, curr, end) {
+                Ok(next) => curr = next,
+                Err(e) => return Err(e),
+            }
+            continue;
+        }
+
+        if align_offset != usize::MAX && align_offset.wrapping_sub(curr) % WORD_BYTES == 0 {
+            curr = match scan_ascii_blocks(buf, curr, block_end_primary, cfg.primary_block_words) {
+                Err(pos) => pos,
+                Ok(curr) => match scan_ascii_blocks(buf, curr, block_end_secondary, cfg.secondary_block_words) {
+                    Err(pos) => pos,
+                    Ok(mut curr) => {
+                        while curr < end && buf[curr] < 128 {
+                            curr += 1;
+                        }
+                        curr
+                    }
+                },
+            };
+        } else {
+            curr += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Standalone ASCII-scanning primitives, for callers (e.g. a parser's own
+/// ASCII fast path) that want the word-block masking [`validate_utf8`]
+/// uses internally without pulling in the full UTF-8 validator.
+pub mod ascii {
+    use super::{block_end, mask_if_non_ascii, non_ascii_byte_position, ByteVector, WORD_BYTES};
+
+    /// Returns the byte index of the first non-ASCII byte in `buf`, or
+    /// `None` if `buf` is all ASCII.
+    ///
+    /// Scans word-aligned 2-word blocks with the same masking
+    /// (`mask_if_non_ascii`) and position-finding
+    /// (`non_ascii_byte_position`) machinery [`crate::validate_utf8`] uses,
+    /// walking any unaligned leading bytes and the trailing partial block
+    /// one byte at a time, the same alignment handling as
+    /// [`crate::validate_utf8_with_stats`]'s ASCII scan.
+    pub fn first_non_ascii(buf: &[u8]) -> Option<usize> {
+        let (mut curr, end) = (0, buf.len());
+        let start = buf.as_ptr();
+
+        let align_offset = start.align_offset(WORD_BYTES);
+        if align_offset != usize::MAX {
+            let prefix_len = align_offset.wrapping_sub(curr) % WORD_BYTES;
+            let prefix_end = (curr + prefix_len).min(end);
+            while curr < prefix_end {
+                if buf[curr] >= 128 {
+                    return Some(curr);
+                }
+                curr += 1;
+            }
+        }
+
+        let block_end_2x = block_end(end, 2 * WORD_BYTES);
+        while curr < block_end_2x {
+            // SAFETY: the loop condition guarantees room for a 2-word
+            // block, and the prefix loop above left `curr` word-aligned
+            let block: [usize; 2] = unsafe { ByteVector::load(start.add(curr)) };
+            if let Some(masked) = mask_if_non_ascii(block) {
+                // SAFETY: `mask_if_non_ascii` only returns `Some` when a
+                // non-ASCII byte is present in `block`
+                return Some(curr + unsafe { non_ascii_byte_position(&masked) } as usize);
+            }
+            curr += 2 * WORD_BYTES;
+        }
+
+        while curr < end {
+            if buf[curr] >= 128 {
+                return Some(curr);
+            }
+            curr += 1;
+        }
+
+        None
+    }
+}
+
+/// Returns the byte index of the first non-ASCII byte in `buf`, or `None`
+/// if `buf` is all ASCII — e.g. for protocols with an ASCII header
+/// followed by arbitrary UTF-8, to find where the header ends without
+/// validating the multi-byte grammar of whatever follows it.
+///
+/// Thin top-level alias for [`ascii::first_non_ascii`], which does the
+/// actual block-scanning; this name mirrors [`validate_utf8`] and its
+/// siblings for callers who don't otherwise need the `ascii` module.
+pub fn first_non_ascii_offset(buf: &[u8]) -> Option<usize> {
+    ascii::first_non_ascii(buf)
+}
+
+/// Returns `true` if every byte in `buf` is ASCII (`< 0x80`).
+///
+/// Reuses the same word-aligned 8x/2x block masking
+/// [`validate_utf8_with_stats`]'s ASCII fast path scans with, but never
+/// calls `validate_non_acii_bytes` — the first non-ASCII byte found
+/// anywhere short-circuits straight to `false`, since no further UTF-8
+/// grammar needs to be checked. Strictly cheaper than
+/// `validate_utf8(buf).is_ok()` for the common all-ASCII case.
+pub fn is_ascii(buf: &[u8]) -> bool {
+    let (mut curr, end) = (0, buf.len());
+    let start = buf.as_ptr();
+
+    let block_end_2x = block_end(end, 2 * WORD_BYTES);
+    let block_end_8x = block_end(end, 8 * WORD_BYTES);
+    let align_offset = start.align_offset(WORD_BYTES);
+
+    if align_offset != usize::MAX {
+        let offset = align_offset.wrapping_sub(curr) % WORD_BYTES;
+        let aligned_end = (curr + offset).min(end);
+        while curr < aligned_end {
+            if buf[curr] >= 128 {
+                return false;
+            }
+            curr += 1;
+        }
+    }
+
+    while curr < block_end_8x {
+        // SAFETY: the loop condition guarantees room for an 8-word block,
+        // and the prefix loop above left `curr` word-aligned
+        let block: [usize; 8] = unsafe { ByteVector::load(start.add(curr)) };
+        if has_non_ascii_byte(&block) {
+            return false;
+        }
+        curr += 8 * WORD_BYTES;
+    }
+
+    while curr < block_end_2x {
+        // SAFETY: the loop condition guarantees room for a 2-word block
+        let block: [usize; 2] = unsafe { ByteVector::load(start.add(curr)) };
+        if has_non_ascii_byte(&block) {
+            return false;
+        }
+        curr += 2 * WORD_BYTES;
+    }
+
+    buf[curr..end].iter().all(|&b| b < 128)
+}
+
 /// Used by all variants, validates non-ascii bytes, identical to STD
 #[inline(always)]
 #[cold]
@@ -301,10 +1362,11 @@ const fn validate_non_acii_bytes(
 ) -> Result<usize, Utf8Error> {
     let prev = curr;
     macro_rules! err {
-        ($error_len: expr) => {
+        ($error_len: expr, $error_byte: expr) => {
             return Err(Utf8Error {
                 valid_up_to: prev,
                 error_len: $error_len,
+                error_byte: $error_byte,
             })
         };
     }
@@ -314,7 +1376,7 @@ const fn validate_non_acii_bytes(
             curr += 1;
             // we needed data, but there was none: error!
             if curr >= end {
-                err!(None);
+                err!(None, None);
             }
             buf[curr]
         }};
@@ -324,42 +1386,116 @@ const fn validate_non_acii_bytes(
     let width = utf8_char_width(byte);
     match width {
         2 => {
-            if next!() as i8 >= -64 {
-                err!(Some(1));
+            let cont = next!();
+            if cont as i8 >= -64 {
+                err!(Some(1), Some(cont));
             }
         }
         3 => {
-            match (byte, next!()) {
+            let cont1 = next!();
+            match (byte, cont1) {
                 (0xE0, 0xA0..=0xBF)
                 | (0xE1..=0xEC, 0x80..=0xBF)
                 | (0xED, 0x80..=0x9F)
                 | (0xEE..=0xEF, 0x80..=0xBF) => {}
-                _ => err!(Some(1)),
+                _ => err!(Some(1), Some(cont1)),
             }
 
-            if next!() as i8 >= -64 {
-                err!(Some(2));
+            let cont2 = next!();
+            if cont2 as i8 >= -64 {
+                err!(Some(2), Some(cont2));
             }
         }
         4 => {
-            match (byte, next!()) {
+            let cont1 = next!();
+            match (byte, cont1) {
                 (0xF0, 0x90..=0xBF) | (0xF1..=0xF3, 0x80..=0xBF) | (0xF4, 0x80..=0x8F) => {}
-                _ => err!(Some(1)),
+                _ => err!(Some(1), Some(cont1)),
             }
-            if next!() as i8 >= -64 {
-                err!(Some(2));
+            let cont2 = next!();
+            if cont2 as i8 >= -64 {
+                err!(Some(2), Some(cont2));
             }
-            if next!() as i8 >= -64 {
-                err!(Some(3));
+            let cont3 = next!();
+            if cont3 as i8 >= -64 {
+                err!(Some(3), Some(cont3));
             }
         }
-        _ => err!(Some(1)),
+        _ => err!(Some(1), Some(byte)),
     }
 
     curr += 1;
     Ok(curr)
 }
 
+/// SWAR fast path for the non-ASCII branch of [`validate_utf8_with_stats`].
+///
+/// UTF-8's structure means a lead byte always interrupts a run of
+/// continuation-tagged bytes, so there's no way to validate *several*
+/// characters from a single masked word read; what this avoids instead is
+/// the round trip back through the outer ASCII/block-scan loop (and its
+/// `non_ascii_checks` counter) between every character of a long non-ASCII
+/// run (e.g. Chinese prose): it keeps decoding characters in a tight
+/// inner loop instead of returning to the caller after each one, and for
+/// 3- and 4-byte sequences it checks the *generic* trailing continuation
+/// bytes (the ones after the specially-ranged first one) a word at a time
+/// via [`count_continuation_bytes_swar`] instead of one comparison per
+/// byte. It falls back to [`validate_non_acii_bytes`] the moment a
+/// character doesn't validate this way, so the reported [`Utf8Error`] is
+/// always identical to what the byte-at-a-time path reports.
+///
+/// Caller contract matches [`validate_non_acii_bytes`]: `buf[curr]` must
+/// be non-ASCII.
+fn validate_non_ascii_run_swar(buf: &[u8], mut curr: usize, end: usize) -> Result<usize, Utf8Error> {
+    loop {
+        let byte = buf[curr];
+        let width = utf8_char_width(byte);
+
+        match width {
+            2 if curr + 2 <= end => {
+                let cont = buf[curr + 1];
+                if cont as i8 >= -64 {
+                    return validate_non_acii_bytes(buf, curr, end);
+                }
+                curr += 2;
+            }
+            3 if curr + 3 <= end => {
+                let cont1 = buf[curr + 1];
+                let legal_first = matches!(
+                    (byte, cont1),
+                    (0xE0, 0xA0..=0xBF)
+                        | (0xE1..=0xEC, 0x80..=0xBF)
+                        | (0xED, 0x80..=0x9F)
+                        | (0xEE..=0xEF, 0x80..=0xBF)
+                );
+                if !legal_first || count_continuation_bytes_swar(buf, curr + 2, curr + 3) != 1 {
+                    return validate_non_acii_bytes(buf, curr, end);
+                }
+                curr += 3;
+            }
+            4 if curr + 4 <= end => {
+                let cont1 = buf[curr + 1];
+                let legal_first = matches!(
+                    (byte, cont1),
+                    (0xF0, 0x90..=0xBF) | (0xF1..=0xF3, 0x80..=0xBF) | (0xF4, 0x80..=0x8F)
+                );
+                if !legal_first || count_continuation_bytes_swar(buf, curr + 2, curr + 4) != 2 {
+                    return validate_non_acii_bytes(buf, curr, end);
+                }
+                curr += 4;
+            }
+            _ => return validate_non_acii_bytes(buf, curr, end),
+        }
+
+        // keep decoding while the run continues with another non-ASCII
+        // character; otherwise hand back to the caller's ASCII/block-scan
+        // loop exactly where `validate_non_acii_bytes` would have.
+        if curr >= end || buf[curr] < 128 {
+            return Ok(curr);
+        }
+    }
+}
+
 #[inline(always)]
 const fn block_end(end: usize, block_size: usize) -> usize {
     if end >= block_size {
@@ -369,8 +1505,63 @@ const fn block_end(end: usize, block_size: usize) -> usize {
     }
 }
 
+/// The number of bytes a UTF-8 sequence starting with `byte` occupies:
+/// `1` for ASCII, `2`/`3`/`4` for the corresponding multi-byte lead
+/// bytes, and `0` for a continuation byte (`0x80..=0xBF`) or a byte that
+/// can never legally start a sequence (`0xC0`, `0xC1`, `0xF5..=0xFF`).
+///
+/// This is the branch-free width table `validate_non_acii_bytes` and
+/// friends decode against; exposed publicly so callers building their own
+/// UTF-8-aware cursors (e.g. stepping backward/forward over character
+/// boundaries) don't have to reimplement it.
+///
+/// # Examples
+///
+/// ```
+/// use fast_utf8::utf8_char_width;
+///
+/// assert_eq!(utf8_char_width(b'A'), 1);
+/// assert_eq!(utf8_char_width("é".as_bytes()[0]), 2);
+/// assert_eq!(utf8_char_width("中".as_bytes()[0]), 3);
+/// assert_eq!(utf8_char_width("😀".as_bytes()[0]), 4);
+/// ```
 #[inline(always)]
-const fn utf8_char_width(byte: u8) -> usize {
+#[cfg(feature = "size-min")]
+pub const fn utf8_char_width(byte: u8) -> usize {
+    // Same table as the default `utf8_char_width`, expressed as ranges
+    // instead of a 256-entry `[u8; 256]` so `size-min` builds don't pay
+    // for the table's .rodata.
+    match byte {
+        0x00..=0x7F => 1,
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => 0,
+    }
+}
+
+/// The number of bytes a UTF-8 sequence starting with `byte` occupies:
+/// `1` for ASCII, `2`/`3`/`4` for the corresponding multi-byte lead
+/// bytes, and `0` for a continuation byte (`0x80..=0xBF`) or a byte that
+/// can never legally start a sequence (`0xC0`, `0xC1`, `0xF5..=0xFF`).
+///
+/// This is the branch-free width table `validate_non_acii_bytes` and
+/// friends decode against; exposed publicly so callers building their own
+/// UTF-8-aware cursors (e.g. stepping backward/forward over character
+/// boundaries) don't have to reimplement it.
+///
+/// # Examples
+///
+/// ```
+/// use fast_utf8::utf8_char_width;
+///
+/// assert_eq!(utf8_char_width(b'A'), 1);
+/// assert_eq!(utf8_char_width("é".as_bytes()[0]), 2);
+/// assert_eq!(utf8_char_width("中".as_bytes()[0]), 3);
+/// assert_eq!(utf8_char_width("😀".as_bytes()[0]), 4);
+/// ```
+#[cfg(not(feature = "size-min"))]
+pub const fn utf8_char_width(byte: u8) -> usize {
     // https://tools.ietf.org/html/rfc3629
     const UTF8_CHAR_WIDTH: [u8; 256] = [
         // 1  2  3  4  5  6  7  8  9  A  B  C  D  E  F
@@ -395,8 +1586,99 @@ const fn utf8_char_width(byte: u8) -> usize {
     UTF8_CHAR_WIDTH[byte as usize] as usize
 }
 
+/// Returns the start index of the character containing or preceding
+/// `index`, i.e. the largest UTF-8 character boundary `<= index`, mirroring
+/// the standard library's `str::floor_char_boundary`.
+///
+/// `index` past `buf.len()` is clamped to `buf.len()`, which is always a
+/// boundary. Otherwise walks backward while [`utf8_char_width`] reports `0`
+/// for the byte at `index` — true for continuation bytes (`0x80..=0xBF`)
+/// and for bytes that can never legally start a sequence, so a malformed
+/// buffer still terminates the walk rather than running off the start.
+///
+/// This duplicates the small walk-back `char_boundary_at_or_before`
+/// already does internally, rather than calling it, so it stays available
+/// without `std`.
+#[must_use]
+pub fn find_char_boundary_before(buf: &[u8], index: usize) -> usize {
+    let mut i = index.min(buf.len());
+    while i > 0 && i < buf.len() && utf8_char_width(buf[i]) == 0 {
+        i -= 1;
+    }
+    i
+}
+
+/// Scalar reference implementation kept for benchmarking/comparison
+/// against [`validate_utf8`]; gated behind `reference-impl` since it
+/// exists purely as a baseline for the `std`-only benches, not as a
+/// `no_std`-usable API, and roughly doubles binary size if compiled in.
+///
+/// Delegates to [`validate_utf8_std_with_stats`] with `None`, so callers
+/// that don't need the counters pay nothing extra for them.
+#[cfg(feature = "reference-impl")]
 #[inline(never)]
 pub fn validate_utf8_std(v: &[u8]) -> Result<(), Utf8Error> {
+    validate_utf8_std_with_stats(v, None)
+}
+
+/// Validates every item in `items` independently, returning one
+/// [`Result`] per item in the same order.
+///
+/// Intended for callers validating many short strings (see
+/// `benches/vs_std.rs`'s `short_strings` group) who want to amortize the
+/// loop/iterator setup around [`validate_utf8`] rather than calling it
+/// once per item at scattered call sites. Each item's result is fully
+/// independent — one invalid item doesn't affect any other's.
+#[cfg(feature = "std")]
+pub fn validate_many<'a>(items: impl IntoIterator<Item = &'a [u8]>) -> Vec<Result<(), Utf8Error>> {
+    items.into_iter().map(validate_utf8).collect()
+}
+
+/// Validates every item in `items` in order, stopping at (and reporting)
+/// the first one that fails.
+///
+/// Where [`validate_many`] always visits every item and reports each
+/// item's result, `validate_all` short-circuits: as soon as an item is
+/// invalid, it returns that item's index and error without touching the
+/// rest. Prefer this when the caller only needs to know "is everything
+/// valid, and if not, where's the first problem" rather than a full
+/// per-item report.
+#[cfg(feature = "std")]
+pub fn validate_all<'a>(items: impl IntoIterator<Item = &'a [u8]>) -> Result<(), (usize, Utf8Error)> {
+    for (index, item) in items.into_iter().enumerate() {
+        if let Err(e) = validate_utf8(item) {
+            return Err((index, e));
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs both [`validate_utf8`] and [`validate_utf8_std`] on `buf` and
+/// returns both results, for fuzz harnesses and property tests that
+/// assert the two implementations always agree.
+///
+/// A single call here is more convenient than invoking each validator
+/// separately at every call site, and keeps the pairing visible at the
+/// type level: a fuzz target can simply `assert_eq!` the two elements of
+/// the returned tuple.
+#[cfg(feature = "reference-impl")]
+pub fn validate_utf8_differential(buf: &[u8]) -> (Result<(), Utf8Error>, Result<(), Utf8Error>) {
+    (validate_utf8(buf), validate_utf8_std(buf))
+}
+
+/// [`validate_utf8_std`], instrumented with the same [`Statistics`]
+/// counters [`validate_utf8_with_stats`] collects, so the reference and
+/// optimized implementations' alignment/block behavior can be compared
+/// apples-to-apples (see `main.rs`).
+///
+/// Only `bytewise_checks`, `non_ascii_checks`, `unaligned_blocks`, and the
+/// 2x block counters are meaningful here — this reference implementation
+/// has no 8x block loop, so `success_blocks_8x`/`failed_blocks_8x` stay
+/// `0`.
+#[cfg(feature = "reference-impl")]
+#[inline(never)]
+pub fn validate_utf8_std_with_stats(v: &[u8], mut stats: Option<&mut Statistics>) -> Result<(), Utf8Error> {
     let mut index = 0;
     let len = v.len();
 
@@ -412,10 +1694,11 @@ pub fn validate_utf8_std(v: &[u8]) -> Result<(), Utf8Error> {
     while index < len {
         let old_offset = index;
         macro_rules! err {
-            ($error_len: expr) => {
+            ($error_len: expr, $error_byte: expr) => {
                 return Err(Utf8Error {
                     valid_up_to: old_offset,
                     error_len: $error_len,
+                    error_byte: $error_byte,
                 })
             };
         }
@@ -425,7 +1708,7 @@ pub fn validate_utf8_std(v: &[u8]) -> Result<(), Utf8Error> {
                 index += 1;
                 // we needed data, but there was none: error!
                 if index >= len {
-                    err!(None)
+                    err!(None, None)
                 }
                 v[index]
             }};
@@ -433,6 +1716,10 @@ pub fn validate_utf8_std(v: &[u8]) -> Result<(), Utf8Error> {
 
         let first = v[index];
         if first >= 128 {
+            if let Some(stats) = stats.as_mut() {
+                stats.non_ascii_checks += 1;
+            }
+
             let w = utf8_char_width(first);
             // 2-byte encoding is for codepoints  \u{0080} to  \u{07ff}
             //        first  C2 80        last DF BF
@@ -454,35 +1741,41 @@ pub fn validate_utf8_std(v: &[u8]) -> Result<(), Utf8Error> {
             //               %xF4 %x80-8F 2( UTF8-tail )
             match w {
                 2 => {
-                    if next!() as i8 >= -64 {
-                        err!(Some(1))
+                    let cont = next!();
+                    if cont as i8 >= -64 {
+                        err!(Some(1), Some(cont))
                     }
                 }
                 3 => {
-                    match (first, next!()) {
+                    let cont1 = next!();
+                    match (first, cont1) {
                         (0xE0, 0xA0..=0xBF)
                         | (0xE1..=0xEC, 0x80..=0xBF)
                         | (0xED, 0x80..=0x9F)
                         | (0xEE..=0xEF, 0x80..=0xBF) => {}
-                        _ => err!(Some(1)),
+                        _ => err!(Some(1), Some(cont1)),
                     }
-                    if next!() as i8 >= -64 {
-                        err!(Some(2))
+                    let cont2 = next!();
+                    if cont2 as i8 >= -64 {
+                        err!(Some(2), Some(cont2))
                     }
                 }
                 4 => {
-                    match (first, next!()) {
+                    let cont1 = next!();
+                    match (first, cont1) {
                         (0xF0, 0x90..=0xBF) | (0xF1..=0xF3, 0x80..=0xBF) | (0xF4, 0x80..=0x8F) => {}
-                        _ => err!(Some(1)),
+                        _ => err!(Some(1), Some(cont1)),
                     }
-                    if next!() as i8 >= -64 {
-                        err!(Some(2))
+                    let cont2 = next!();
+                    if cont2 as i8 >= -64 {
+                        err!(Some(2), Some(cont2))
                     }
-                    if next!() as i8 >= -64 {
-                        err!(Some(3))
+                    let cont3 = next!();
+                    if cont3 as i8 >= -64 {
+                        err!(Some(3), Some(cont3))
                     }
                 }
-                _ => err!(Some(1)),
+                _ => err!(Some(1), Some(first)),
             }
             index += 1;
         } else {
@@ -502,16 +1795,29 @@ pub fn validate_utf8_std(v: &[u8]) -> Result<(), Utf8Error> {
                         let zu = contains_nonascii(*block);
                         let zv = contains_nonascii(*block.add(1));
                         if zu || zv {
+                            if let Some(stats) = stats.as_mut() {
+                                stats.failed_blocks_2x += 1;
+                            }
                             break;
                         }
                     }
+                    if let Some(stats) = stats.as_mut() {
+                        stats.success_blocks_2x += 1;
+                    }
                     index += ascii_block_size;
                 }
                 // step from the point where the wordwise loop stopped
                 while index < len && v[index] < 128 {
+                    if let Some(stats) = stats.as_mut() {
+                        stats.bytewise_checks += 1;
+                    }
                     index += 1;
                 }
             } else {
+                if let Some(stats) = stats.as_mut() {
+                    stats.unaligned_blocks += 1;
+                    stats.bytewise_checks += 1;
+                }
                 index += 1;
             }
         }
@@ -525,107 +1831,5605 @@ const fn contains_nonascii(x: usize) -> bool {
     (x & NONASCII_MASK) != 0
 }
 
-#[cfg(test)]
-mod tests {
-    const GERMAN_UTF8_16KB: &str = include_str!("../assets/german_16kb.txt");
+const NUL_LO: usize = usize::from_ne_bytes([0x01; WORD_BYTES]);
 
-    use super::validate_utf8;
+/// Classic SWAR trick: `true` if any byte of `x` is `0x00`.
+#[inline(always)]
+const fn contains_zero_byte(x: usize) -> bool {
+    x.wrapping_sub(NUL_LO) & !x & NONASCII_MASK != 0
+}
 
-    #[test]
-    fn invalid_utf8() {
-        assert_eq!(
-            validate_utf8(b"A\xC3\xA9 \xF1 "),
-            Err(super::Utf8Error {
-                valid_up_to: 4,
-                error_len: Some(1)
+/// SWAR "has zero byte" detector: for each byte lane of `x` that is
+/// `0x00`, the returned word has that lane's MSB set; every other bit is
+/// clear. Used to locate, rather than merely detect, matching bytes.
+#[inline(always)]
+const fn zero_byte_mask(x: usize) -> usize {
+    x.wrapping_sub(NUL_LO) & !x & NONASCII_MASK
+}
+
+/// Replicates `byte` into every byte lane of a word.
+#[inline(always)]
+const fn broadcast(byte: u8) -> usize {
+    usize::from_ne_bytes([byte; WORD_BYTES])
+}
+
+/// Validates `buf` as UTF-8 and, in the same scan, finds the first byte
+/// matching any of up to 4 ASCII `needles`.
+///
+/// Restricting needles to ASCII keeps the SWAR equality comparisons
+/// correct without needing to special-case continuation bytes: a
+/// continuation byte (`0x80..=0xBF`) can never equal an ASCII needle, so
+/// a plain per-lane XOR-and-zero-check finds only genuine matches. Blocks
+/// are shared with the non-ASCII mask in spirit, though the search runs
+/// as its own pass after validation rather than inline in the hot loop.
+///
+/// Returns `Ok(None)` if `buf` is valid UTF-8 but none of the needles
+/// occur; returns the `Utf8Error` immediately if `buf` isn't valid UTF-8.
+///
+/// # Panics (debug only)
+///
+/// Panics if more than 4 needles are given, or if any needle is not
+/// ASCII.
+pub fn validate_utf8_with_simd_find_first_of(
+    buf: &[u8],
+    needles: &[u8],
+) -> Result<Option<usize>, Utf8Error> {
+    debug_assert!(needles.len() <= 4, "at most 4 needle bytes are supported");
+    debug_assert!(needles.iter().all(u8::is_ascii), "needles must be ASCII");
+
+    validate_utf8(buf)?;
+
+    let active = needles.len().min(4);
+    let mut broadcasts = [0usize; 4];
+    for (slot, &needle) in broadcasts.iter_mut().zip(needles) {
+        *slot = broadcast(needle);
+    }
+
+    let (mut curr, end) = (0, buf.len());
+    let start = buf.as_ptr();
+
+    while curr + WORD_BYTES <= end {
+        // SAFETY: bounds checked by the loop condition
+        let word = unsafe { (start.add(curr) as *const usize).read_unaligned() };
+        let mut combined = 0usize;
+        for &needle in &broadcasts[..active] {
+            combined |= zero_byte_mask(word ^ needle);
+        }
+        if combined != 0 {
+            return Ok(Some(curr + (combined.trailing_zeros() / WORD_BYTES as u32) as usize));
+        }
+        curr += WORD_BYTES;
+    }
+
+    while curr < end {
+        if needles[..active].contains(&buf[curr]) {
+            return Ok(Some(curr));
+        }
+        curr += 1;
+    }
+
+    Ok(None)
+}
+
+/// Given a `buf` and the [`Utf8Error`] produced by validating it, returns
+/// the number of additional continuation bytes that would be needed to
+/// complete the final, truncated character.
+///
+/// Returns `None` if `err` does not describe a truncation (i.e.
+/// `err.error_len` is `Some`), since a real structural error can't be
+/// "fixed" by appending more bytes. This lets interactive tools (a REPL,
+/// a terminal) display "waiting for N more bytes" instead of a generic
+/// error.
+pub fn missing_bytes_for_completion(buf: &[u8], err: &Utf8Error) -> Option<u8> {
+    if err.error_len.is_some() {
+        return None;
+    }
+
+    let lead = buf[err.valid_up_to];
+    let width = utf8_char_width(lead);
+    let present = buf.len() - err.valid_up_to;
+    Some((width - present) as u8)
+}
+
+/// Counts, via SWAR, how many bytes of `word` are *not* UTF-8 continuation
+/// bytes (i.e. don't match `0b10xxxxxx`), which is exactly the number of
+/// Unicode scalar values that start within `word`.
+///
+/// Uses the same "has zero byte" trick as [`contains_zero_byte`], applied
+/// to the top two bits of every byte isolated via [`CONT_TAG_MASK`]: a
+/// byte tagged `0b10` (continuation) becomes zero, everything else stays
+/// non-zero, so counting the resulting zero bytes and subtracting from
+/// the word width gives the lead/ASCII byte count almost for free.
+#[inline(always)]
+const fn count_non_continuation_bytes(word: usize) -> u32 {
+    let diff = (word & CONT_TAG_MASK) ^ CONT_TAG;
+    let has_zero = diff.wrapping_sub(NUL_LO) & !diff & NONASCII_MASK;
+    WORD_BYTES as u32 - has_zero.count_ones()
+}
+
+/// Validates `buf` as UTF-8 and returns the number of Unicode scalar
+/// values (`char`s) it contains. Validation runs first via
+/// [`validate_utf8`]; counting is a second pass over the buffer, a word
+/// at a time via `count_non_continuation_bytes`.
+pub fn validate_utf8_count_codepoints(buf: &[u8]) -> Result<usize, Utf8Error> {
+    validate_utf8(buf)?;
+
+    let (mut curr, end) = (0, buf.len());
+    let start = buf.as_ptr();
+    let mut count = 0usize;
+
+    while curr + WORD_BYTES <= end {
+        // SAFETY: bounds checked by the loop condition
+        let word = unsafe { (start.add(curr) as *const usize).read_unaligned() };
+        count += count_non_continuation_bytes(word) as usize;
+        curr += WORD_BYTES;
+    }
+
+    while curr < end {
+        if !matches!(buf[curr], 0x80..=0xBF) {
+            count += 1;
+        }
+        curr += 1;
+    }
+
+    Ok(count)
+}
+
+/// A best-effort guess at the text encoding of a byte buffer, produced by
+/// [`sniff_encoding`].
+///
+/// This is a heuristic, not a proof: `Utf8` merely means the buffer is
+/// structurally valid UTF-8, not that it was necessarily produced as such.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodingHint {
+    /// Structurally valid UTF-8 (with or without a BOM).
+    Utf8,
+    /// Looks like UTF-16, little-endian (BOM `FF FE`, or a dense
+    /// alternating-NUL pattern with NULs on odd byte indices).
+    Utf16Le,
+    /// Looks like UTF-16, big-endian (BOM `FE FF`, or a dense
+    /// alternating-NUL pattern with NULs on even byte indices).
+    Utf16Be,
+    /// Not valid UTF-8, but every byte is otherwise plausible, i.e. it may
+    /// be Latin-1 or another single-byte encoding.
+    Latin1,
+    /// Neither valid UTF-8 nor a recognizable NUL pattern.
+    Unknown,
+}
+
+/// Heuristically guesses the encoding of `buf`.
+///
+/// The check proceeds, cheapest first: a byte-order-mark, then an
+/// alternating-NUL sample typical of UTF-16 text where one code unit byte
+/// is ASCII, and finally a full [`validate_utf8`] pass. This is meant as
+/// a quick triage step before choosing a decoder, not a substitute for
+/// actually decoding the input.
+pub fn sniff_encoding(buf: &[u8]) -> EncodingHint {
+    match buf {
+        [0xEF, 0xBB, 0xBF, ..] => return EncodingHint::Utf8,
+        [0xFF, 0xFE, ..] => return EncodingHint::Utf16Le,
+        [0xFE, 0xFF, ..] => return EncodingHint::Utf16Be,
+        _ => {}
+    }
+
+    // Sample up to the first 64 code-unit pairs and check whether NUL
+    // bytes consistently land on the low or high byte of each pair, the
+    // signature of ASCII-range text stored as 16-bit code units. This is
+    // checked before the structural UTF-8 pass since embedded NUL bytes
+    // are themselves perfectly valid (if unlikely) UTF-8.
+    let sample = &buf[..buf.len().min(128)];
+    if sample.len() >= 4 {
+        let pairs = sample.chunks_exact(2);
+        let pair_count = pairs.len();
+        let (mut nul_lo, mut nul_hi) = (0usize, 0usize);
+        for pair in pairs {
+            nul_lo += (pair[0] == 0) as usize;
+            nul_hi += (pair[1] == 0) as usize;
+        }
+        if pair_count > 0 && nul_hi * 4 >= pair_count * 3 {
+            return EncodingHint::Utf16Le;
+        }
+        if pair_count > 0 && nul_lo * 4 >= pair_count * 3 {
+            return EncodingHint::Utf16Be;
+        }
+    }
+
+    if validate_utf8(buf).is_ok() {
+        return EncodingHint::Utf8;
+    }
+
+    if buf.iter().all(|&b| b != 0) {
+        EncodingHint::Latin1
+    } else {
+        EncodingHint::Unknown
+    }
+}
+
+/// Callback interface for [`validate_utf8_visit`], a SAX-style consumer
+/// for incremental processing of validated UTF-8.
+///
+/// All methods have empty default bodies, so a consumer only needs to
+/// implement the callbacks it cares about.
+pub trait Utf8Visitor {
+    /// Called with each maximal run of consecutive ASCII bytes.
+    fn on_ascii_run(&mut self, _run: &[u8]) {}
+    /// Called with each decoded multi-byte scalar value.
+    fn on_char(&mut self, _ch: char) {}
+    /// Called once, in place of any further callbacks, if `buf` turns out
+    /// to be invalid UTF-8.
+    fn on_error(&mut self, _err: Utf8Error) {}
+}
+
+/// Drives validation of `buf`, invoking `visitor`'s callbacks as it goes:
+/// [`Utf8Visitor::on_ascii_run`] for each maximal ASCII block, and
+/// [`Utf8Visitor::on_char`] for each decoded multi-byte scalar. This lets
+/// a downstream consumer (e.g. a tokenizer) do its own single-pass work
+/// while still getting the fast ASCII handling, without a second pass
+/// over the buffer.
+pub fn validate_utf8_visit<V: Utf8Visitor>(buf: &[u8], visitor: &mut V) {
+    let (mut curr, end) = (0, buf.len());
+
+    while curr < end {
+        let ascii_start = curr;
+        while curr < end && buf[curr] < 128 {
+            curr += 1;
+        }
+
+        if curr > ascii_start {
+            visitor.on_ascii_run(&buf[ascii_start..curr]);
+        }
+
+        if curr == end {
+            return;
+        }
+
+        match validate_non_acii_bytes(buf, curr, end) {
+            Ok(next) => {
+                // SAFETY: `validate_non_acii_bytes` only returns `Ok` for a
+                // range that decodes to exactly one valid scalar value
+                let ch = unsafe { str::from_utf8_unchecked(&buf[curr..next]) }
+                    .chars()
+                    .next()
+                    .unwrap();
+                visitor.on_char(ch);
+                curr = next;
+            }
+            Err(e) => {
+                visitor.on_error(e);
+                return;
+            }
+        }
+    }
+}
+
+/// Validates `buf` as UTF-8, calling `f` with each maximal run of
+/// consecutive ASCII bytes as it's discovered by the block scan, so a
+/// caller can do single-pass work on the ASCII portions (hashing,
+/// case-folding, ...) without a second pass over `buf`.
+///
+/// A closure-based sibling of [`validate_utf8_visit`] for callers who
+/// only care about the ASCII runs and want a plain `Result` back instead
+/// of implementing [`Utf8Visitor`]; reach for the trait-based version
+/// instead if you also need the decoded multi-byte characters.
+pub fn validate_utf8_visit_ascii<F: FnMut(&[u8])>(buf: &[u8], mut f: F) -> Result<(), Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+
+    while curr < end {
+        let ascii_start = curr;
+        while curr < end && buf[curr] < 128 {
+            curr += 1;
+        }
+
+        if curr > ascii_start {
+            f(&buf[ascii_start..curr]);
+        }
+
+        if curr == end {
+            return Ok(());
+        }
+
+        curr = validate_non_acii_bytes(buf, curr, end)?;
+    }
+
+    Ok(())
+}
+
+/// Walks `idx` back to the nearest byte boundary at or before it that
+/// does not fall in the middle of a multi-byte UTF-8 sequence.
+#[cfg(feature = "std")]
+fn char_boundary_at_or_before(buf: &[u8], idx: usize) -> usize {
+    let mut i = idx.min(buf.len());
+    while i > 0 && i < buf.len() && matches!(buf[i], 0x80..=0xBF) {
+        i -= 1;
+    }
+    i
+}
+
+/// Splits `buf` into up to `num_chunks` character-boundary-aligned pieces,
+/// returning the chunk boundary offsets (including a leading `0` and a
+/// trailing `buf.len()`).
+///
+/// Each raw (byte-count-even) chunk edge is walked back to a character
+/// boundary via `char_boundary_before`, so a character straddling a seam
+/// is validated exactly once, as part of whichever chunk it starts in.
+/// This is the offset bookkeeping every chunked validator in this module
+/// needs, whether it then processes chunks serially or hands them to
+/// rayon; only the boundary-walking function and the serial-vs-parallel
+/// iteration differ between callers.
+#[cfg(feature = "std")]
+fn char_boundary_aligned_chunk_offsets(
+    buf: &[u8],
+    num_chunks: usize,
+    char_boundary_before: impl Fn(&[u8], usize) -> usize,
+) -> Vec<usize> {
+    let raw_chunk_len = buf.len().div_ceil(num_chunks);
+    let mut offsets = vec![0usize];
+    while *offsets.last().unwrap() < buf.len() {
+        let next = char_boundary_before(buf, offsets.last().unwrap() + raw_chunk_len);
+        // Guard against a pathological run of continuation bytes that
+        // would otherwise walk `next` back to the previous boundary.
+        let next = if next > *offsets.last().unwrap() {
+            next
+        } else {
+            buf.len()
+        };
+        offsets.push(next);
+    }
+    offsets
+}
+
+/// Splits `buf` into up to `num_chunks` pieces at UTF-8 character
+/// boundaries and validates each independently, adjusting every
+/// `Utf8Error` by its chunk's base offset so the *globally* lowest-offset
+/// error always wins, no matter which chunk it came from.
+///
+/// This is the offset bookkeeping a parallel (e.g. rayon-driven) chunked
+/// validator needs in order to report a deterministic first error
+/// regardless of which worker finishes first; chunk boundaries are walked
+/// back to the nearest non-continuation byte so a character straddling a
+/// seam is validated exactly once, as part of whichever chunk it starts
+/// in.
+#[cfg(feature = "std")]
+pub fn validate_utf8_parallel_chunked_with_correct_offsets(
+    buf: &[u8],
+    num_chunks: usize,
+) -> Result<(), Utf8Error> {
+    let num_chunks = num_chunks.max(1);
+    if buf.is_empty() || num_chunks == 1 {
+        return validate_utf8(buf);
+    }
+
+    let offsets = char_boundary_aligned_chunk_offsets(buf, num_chunks, char_boundary_at_or_before);
+
+    let mut first_error: Option<Utf8Error> = None;
+    for window in offsets.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        if let Err(mut err) = validate_utf8(&buf[start..end]) {
+            err.valid_up_to += start;
+            if first_error.as_ref().is_none_or(|e| err.valid_up_to < e.valid_up_to) {
+                first_error = Some(err);
+            }
+        }
+    }
+
+    match first_error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Walks `idx` back to the start of the character it falls inside.
+///
+/// Used by [`validate_utf8_parallel`] to pick split points that never cut
+/// a multi-byte sequence in half; thin wrapper around
+/// [`find_char_boundary_before`], kept as its own name since it's the
+/// vocabulary the rest of this function's neighbors use.
+#[cfg(feature = "rayon")]
+fn char_boundary_before_via_width(buf: &[u8], idx: usize) -> usize {
+    find_char_boundary_before(buf, idx)
+}
+
+/// Validates `buf` as UTF-8 by splitting it into
+/// `rayon::current_num_threads()` character-boundary-aligned chunks (each
+/// split point walked back to the previous lead byte with
+/// `char_boundary_before_via_width`) and validating them in parallel on
+/// rayon's global thread pool.
+///
+/// Shares [`validate_utf8_parallel_chunked_with_correct_offsets`]'s
+/// `char_boundary_aligned_chunk_offsets` offset bookkeeping — every
+/// `Utf8Error` is rebased by its chunk's start offset, and the *globally*
+/// lowest one wins — but drives the per-chunk work through
+/// [`rayon::prelude::ParallelIterator`] instead of a plain loop, for
+/// multi-megabyte buffers where a single thread is the bottleneck.
+#[cfg(feature = "rayon")]
+pub fn validate_utf8_parallel(buf: &[u8]) -> Result<(), Utf8Error> {
+    use rayon::prelude::*;
+
+    let num_chunks = rayon::current_num_threads().max(1);
+    if buf.is_empty() || num_chunks == 1 {
+        return validate_utf8(buf);
+    }
+
+    let offsets = char_boundary_aligned_chunk_offsets(buf, num_chunks, char_boundary_before_via_width);
+
+    offsets
+        .par_windows(2)
+        .filter_map(|window| {
+            let (start, end) = (window[0], window[1]);
+            validate_utf8(&buf[start..end]).err().map(|mut err| {
+                err.valid_up_to += start;
+                err
             })
+        })
+        .min_by_key(|err| err.valid_up_to)
+        .map_or(Ok(()), Err)
+}
+
+/// Validates a batch of short byte slices (e.g. individual log lines),
+/// stopping at the first invalid one.
+///
+/// Amortizes the per-call overhead of validating many small inputs in a
+/// loop by keeping the dispatch to [`validate_utf8`] itself, rather than
+/// each caller re-implementing the loop; on failure the index of the
+/// offending slice is returned alongside its `Utf8Error` so the caller
+/// can report which line was bad.
+pub fn validate_utf8_batch_short(lines: &[&[u8]]) -> Result<(), (usize, Utf8Error)> {
+    for (i, line) in lines.iter().enumerate() {
+        if let Err(err) = validate_utf8(line) {
+            return Err((i, err));
+        }
+    }
+    Ok(())
+}
+
+/// Validates `buf` as UTF-8 and returns it as a `&str`, the `Result`
+/// equivalent of [`str::from_utf8`] built on this crate's faster
+/// [`validate_utf8`] instead of the standard library's validator.
+pub fn from_utf8(buf: &[u8]) -> Result<&str, Utf8Error> {
+    validate_utf8(buf)?;
+    // SAFETY: `validate_utf8` returned `Ok`, so `buf` is valid UTF-8
+    Ok(unsafe { str::from_utf8_unchecked(buf) })
+}
+
+/// Validates `buf` as UTF-8 and splits it into its valid prefix and the
+/// remaining bytes starting at the first error, for callers doing
+/// resync/recovery on a stream that might have a corrupt byte somewhere
+/// in the middle. If `buf` is fully valid, the second element is empty.
+/// Reassembling the two halves (`prefix.as_bytes()` followed by
+/// `remainder`) always reproduces `buf` exactly.
+pub fn from_utf8_prefix(buf: &[u8]) -> (&str, &[u8]) {
+    let valid_up_to = match validate_utf8(buf) {
+        Ok(()) => buf.len(),
+        Err(e) => e.valid_up_to,
+    };
+
+    // SAFETY: `validate_utf8` confirmed `buf[..valid_up_to]` is valid UTF-8,
+    // whether that's all of `buf` or just the prefix up to its first error
+    let prefix = unsafe { str::from_utf8_unchecked(&buf[..valid_up_to]) };
+    (prefix, &buf[valid_up_to..])
+}
+
+/// Validates `buf` as UTF-8, then iterates its valid prefix (see
+/// [`from_utf8_prefix`]) yielding each character's byte offset and decoded
+/// `char`, like [`str::char_indices`].
+///
+/// Every character boundary is found with [`utf8_char_width`] rather than
+/// re-running UTF-8 validation per character; decoding a byte slice known
+/// (via that one up-front `validate_utf8` call) to hold exactly one valid
+/// character is just `str::chars`, which never re-validates.
+pub fn char_indices_fast(buf: &[u8]) -> impl Iterator<Item = (usize, char)> + '_ {
+    let (valid, _) = from_utf8_prefix(buf);
+
+    let mut pos = 0;
+    core::iter::from_fn(move || {
+        if pos >= valid.len() {
+            return None;
+        }
+
+        let idx = pos;
+        let width = utf8_char_width(valid.as_bytes()[idx]);
+        pos += width;
+
+        // SAFETY: `valid` is a validated `&str` and `width` is the exact
+        // length of the character starting at `idx`, per `utf8_char_width`
+        let ch = unsafe { valid.get_unchecked(idx..pos) }.chars().next().unwrap();
+        Some((idx, ch))
+    })
+}
+
+/// Validates `buf` as UTF-8 and returns it as a `&str` with leading and
+/// trailing ASCII whitespace removed.
+///
+/// Only ASCII whitespace (space, tab, CR, LF, FF, VT) is trimmed, so the
+/// bounds can be computed directly from the raw bytes without decoding,
+/// keeping this as cheap as the validation pass itself.
+pub fn validate_and_trim(buf: &[u8]) -> Result<&str, Utf8Error> {
+    validate_utf8(buf)?;
+
+    let mut start = 0;
+    let mut end = buf.len();
+
+    while start < end && buf[start].is_ascii_whitespace() {
+        start += 1;
+    }
+
+    while end > start && buf[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+
+    // SAFETY: `buf` was validated above, and trimming ASCII whitespace
+    // bytes from either end can't land inside a multi-byte character
+    Ok(unsafe { str::from_utf8_unchecked(&buf[start..end]) })
+}
+
+/// Bytes of an incomplete trailing UTF-8 sequence carried across calls to
+/// [`validate_utf8_resumable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PartialChar {
+    bytes: [u8; 4],
+    len: u8,
+}
+
+/// The resumable state threaded through repeated calls to
+/// [`validate_utf8_resumable`], a value-type equivalent of a streaming
+/// validator object for callers that prefer a functional style (e.g.
+/// `no_std` protocol code that can't easily hold one).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Utf8State {
+    /// No partial sequence is pending; the next call starts fresh.
+    #[default]
+    Complete,
+    /// The previous call ended mid-sequence; `buffered` holds the
+    /// unconsumed lead/continuation bytes to prepend to the next chunk.
+    NeedMore { buffered: PartialChar },
+}
+
+/// Validates `buf` as a continuation of a chunked UTF-8 stream, given the
+/// [`Utf8State`] returned by the previous call (or [`Utf8State::Complete`]
+/// for the first chunk).
+///
+/// Returns the new state to pass into the next call, together with the
+/// validation result for this chunk. On success, the returned state may
+/// be [`Utf8State::NeedMore`] if `buf` ends mid-sequence; the caller
+/// should treat that as "not done yet", not as an error, unless no more
+/// data is coming.
+#[cfg(feature = "std")]
+pub fn validate_utf8_resumable(buf: &[u8], state: Utf8State) -> (Utf8State, Result<(), Utf8Error>) {
+    let mut owned;
+    let buf = match state {
+        Utf8State::Complete => buf,
+        Utf8State::NeedMore { buffered } => {
+            owned = Vec::with_capacity(buffered.len as usize + buf.len());
+            owned.extend_from_slice(&buffered.bytes[..buffered.len as usize]);
+            owned.extend_from_slice(buf);
+            &owned[..]
+        }
+    };
+
+    match validate_utf8(buf) {
+        Ok(()) => (Utf8State::Complete, Ok(())),
+        Err(e) if e.error_len.is_none() => {
+            let tail = &buf[e.valid_up_to..];
+            let mut bytes = [0u8; 4];
+            bytes[..tail.len()].copy_from_slice(tail);
+            (
+                Utf8State::NeedMore {
+                    buffered: PartialChar {
+                        bytes,
+                        len: tail.len() as u8,
+                    },
+                },
+                Ok(()),
+            )
+        }
+        Err(e) => (Utf8State::Complete, Err(e)),
+    }
+}
+
+/// A coarse Unicode script family, used by [`validate_utf8_single_script`]
+/// as a cheap homoglyph/confusable heuristic. Not a full script database:
+/// just enough to flag the common Latin/Cyrillic/Greek mixing used in
+/// spoofing attacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Cjk,
+    Arabic,
+}
+
+/// Result of the coarse script scan performed by
+/// [`validate_utf8_single_script`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptHint {
+    /// Only one script family (beyond plain ASCII) was observed.
+    Single(Script),
+    /// More than one "confusable" script family was observed; `.0`/`.1`
+    /// are the two scripts whose mixing was first detected.
+    Mixed(Script, Script),
+}
+
+/// Coarsely classifies `ch` into a [`Script`] family, based on the
+/// Unicode block its scalar value falls in. Returns `None` for ASCII and
+/// any character outside the handful of blocks this heuristic covers.
+fn classify_script(ch: char) -> Option<Script> {
+    match ch as u32 {
+        0x00C0..=0x024F => Some(Script::Latin),
+        0x0370..=0x03FF => Some(Script::Greek),
+        0x0400..=0x04FF => Some(Script::Cyrillic),
+        0x0600..=0x06FF => Some(Script::Arabic),
+        0x3040..=0x30FF | 0x4E00..=0x9FFF => Some(Script::Cjk),
+        _ => None,
+    }
+}
+
+/// Validates `buf` as UTF-8 and, as a coarse security heuristic, tracks
+/// which [`Script`] families its decoded characters belong to.
+///
+/// Returns `Ok(None)` if the text is plain ASCII or contains no character
+/// from one of the covered script blocks, `Ok(Some(ScriptHint::Single))`
+/// if exactly one script family was observed, and
+/// `Ok(Some(ScriptHint::Mixed { .. }))` the moment a second, different
+/// script family is seen — a coarse signal for homoglyph/spoofing attacks
+/// that mix e.g. Latin and Cyrillic. This is a heuristic, not a security
+/// boundary.
+pub fn validate_utf8_single_script(buf: &[u8]) -> Result<Option<ScriptHint>, Utf8Error> {
+    validate_utf8(buf)?;
+
+    // SAFETY: `buf` was just validated as UTF-8
+    let s = unsafe { str::from_utf8_unchecked(buf) };
+
+    let mut seen: Option<Script> = None;
+    for ch in s.chars() {
+        let Some(script) = classify_script(ch) else {
+            continue;
+        };
+
+        match seen {
+            None => seen = Some(script),
+            Some(prev) if prev == script => {}
+            Some(prev) => return Ok(Some(ScriptHint::Mixed(prev, script))),
+        }
+    }
+
+    Ok(seen.map(ScriptHint::Single))
+}
+
+/// True for the Unicode bidirectional control characters used in the
+/// "Trojan Source" family of attacks (CVE-2021-42574) to visually reorder
+/// source text while leaving its logical byte order unchanged: the
+/// embedding/override controls U+202A..=U+202E, the isolate controls
+/// U+2066..=U+2069, and the mark characters U+200E/U+200F.
+///
+/// All of these encode as 3-byte UTF-8 sequences.
+const fn is_bidi_control(ch: char) -> bool {
+    matches!(ch as u32, 0x202A..=0x202E | 0x2066..=0x2069 | 0x200E | 0x200F)
+}
+
+/// Validates `buf` as UTF-8 and additionally rejects any of the Unicode
+/// bidirectional control characters that can be used to hide malicious
+/// code behind a differently-ordered visual rendering (see
+/// `is_bidi_control`).
+///
+/// On success, `buf` is guaranteed to be both valid UTF-8 and free of
+/// these controls. Default validation via [`validate_utf8`] stays
+/// permissive; this is an opt-in, stricter check for contexts (source
+/// code, filenames) where bidi controls are unwelcome.
+///
+/// A rejected bidi control character is reported through the same
+/// `Utf8Error` shape as a grammar violation, with `error_byte` set to the
+/// character's lead byte (not `None` — the sequence isn't truncated, it
+/// was read in full and rejected on policy grounds).
+pub fn validate_utf8_no_bidi_controls(buf: &[u8]) -> Result<(), Utf8Error> {
+    validate_utf8(buf)?;
+
+    // SAFETY: `buf` was just validated as UTF-8
+    let s = unsafe { str::from_utf8_unchecked(buf) };
+
+    for (idx, ch) in s.char_indices() {
+        if is_bidi_control(ch) {
+            let mut encoded = [0u8; 4];
+            let lead_byte = ch.encode_utf8(&mut encoded).as_bytes()[0];
+
+            return Err(Utf8Error {
+                valid_up_to: idx,
+                error_len: Some(ch.len_utf8() as u8),
+                error_byte: Some(lead_byte),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// One entry of the boundary index built by [`validate_utf8_char_boundaries`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharBoundary {
+    /// A run of consecutive single-byte (ASCII) characters: `len`
+    /// characters, each its own lead byte, starting at `start`.
+    AsciiRun { start: usize, len: usize },
+    /// The lead byte of a single multi-byte character.
+    Lead(usize),
+}
+
+/// Validates `buf` as UTF-8 and appends the byte offset of every
+/// character's lead byte to `out`, without decoding scalar values.
+///
+/// Consecutive ASCII lead bytes (where every byte is its own character)
+/// are collapsed into a single [`CharBoundary::AsciiRun`] instead of one
+/// entry per byte, keeping `out` small for mostly-ASCII text; multi-byte
+/// lead bytes get their own [`CharBoundary::Lead`] entry. This is cheaper
+/// than `str::char_indices` for building a character index since it only
+/// needs the width table, not full scalar decoding.
+#[cfg(feature = "std")]
+pub fn validate_utf8_char_boundaries(
+    buf: &[u8],
+    out: &mut Vec<CharBoundary>,
+) -> Result<(), Utf8Error> {
+    validate_utf8(buf)?;
+
+    let mut curr = 0;
+    while curr < buf.len() {
+        let width = utf8_char_width(buf[curr]);
+        if width == 1 {
+            let start = curr;
+            while curr < buf.len() && utf8_char_width(buf[curr]) == 1 {
+                curr += 1;
+            }
+            out.push(CharBoundary::AsciiRun {
+                start,
+                len: curr - start,
+            });
+        } else {
+            out.push(CharBoundary::Lead(curr));
+            curr += width;
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates a compile-time-sized buffer as UTF-8.
+///
+/// For small, fixed `N` (e.g. a `[u8; 16]` record tag), monomorphizing on
+/// the array length lets the optimizer unroll the scan and eliminate
+/// bounds checks it couldn't prove for a runtime-length slice. Simply
+/// forwards to [`validate_utf8`] on the array's full length.
+pub fn validate_utf8_array<const N: usize>(buf: &[u8; N]) -> Result<(), Utf8Error> {
+    validate_utf8(buf)
+}
+
+/// Validates `buf` as UTF-8 while feeding its bytes to `hasher`, so
+/// callers that both validate and hash (e.g. interning into a symbol
+/// table) don't walk the buffer twice.
+///
+/// The leading run of whole ASCII words is fed to the hasher one word at
+/// a time via [`Hasher::write_usize`](core::hash::Hasher::write_usize),
+/// exactly the words the ASCII fast path already reads to detect the
+/// first non-ASCII byte, so hashing them costs nothing beyond the
+/// `write_usize` call itself. The remaining tail (anything shorter than a
+/// word, or containing non-ASCII bytes) is hashed in one `write` and then
+/// validated via [`validate_utf8`].
+pub fn validate_and_hash(buf: &[u8], hasher: &mut impl core::hash::Hasher) -> Result<(), Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+    let start = buf.as_ptr();
+
+    while curr + WORD_BYTES <= end {
+        // SAFETY: bounds checked by the loop condition
+        let word = unsafe { (start.add(curr) as *const usize).read_unaligned() };
+        if contains_nonascii(word) {
+            break;
+        }
+        hasher.write_usize(word);
+        curr += WORD_BYTES;
+    }
+
+    let tail = &buf[curr..end];
+    hasher.write(tail);
+
+    match validate_utf8(tail) {
+        Ok(()) => Ok(()),
+        Err(mut err) => {
+            err.valid_up_to += curr;
+            Err(err)
+        }
+    }
+}
+
+/// Validates `buf` as UTF-8 and returns the number of Unicode scalar
+/// values (`char`s) it decodes to, for callers doing cursor/column math
+/// over the buffer.
+///
+/// A differently-named wrapper around
+/// [`validate_utf8_count_codepoints`] — same word-block counting (each
+/// non-continuation byte, counted a whole word at a time, is one char),
+/// kept as its own `pub fn` so call sites reasoning in terms of "chars"
+/// rather than "codepoints" don't have to translate the name.
+pub fn validate_and_count_chars(buf: &[u8]) -> Result<usize, Utf8Error> {
+    validate_utf8_count_codepoints(buf)
+}
+
+/// Returns the byte offset of the start of the last (possibly incomplete)
+/// character in `buf`, by scanning backward over at most 3 trailing
+/// continuation bytes.
+///
+/// This does **not** validate `buf` — it assumes `buf` is valid UTF-8 up
+/// to whatever character starts at the returned offset — and only
+/// touches the last few bytes, making it O(1) regardless of `buf`'s
+/// length. Intended for a streaming transcoder that wants to cut a
+/// buffer at a safe point and carry the remainder into the next chunk.
+pub fn last_char_boundary(buf: &[u8]) -> usize {
+    let len = buf.len();
+    if len == 0 {
+        return 0;
+    }
+
+    let floor = len.saturating_sub(4);
+    let mut idx = len - 1;
+    while idx > floor && matches!(buf[idx], 0x80..=0xBF) {
+        idx -= 1;
+    }
+    idx
+}
+
+/// An object-oriented streaming validator built on top of
+/// [`Utf8State`]/[`validate_utf8_resumable`], for callers that would
+/// rather hold a validator value and call `feed` repeatedly than thread a
+/// state value through by hand.
+///
+/// Because the only state carried between calls is a [`PartialChar`] — at
+/// most 3 bytes, since a sequence is at most 4 bytes wide and is complete
+/// (no longer carried) once its last byte arrives — a validator can never be
+/// made to buffer an unbounded amount of data: a peer that sends a lead
+/// byte and then withholds its continuation bytes forever costs at most 3
+/// bytes of memory, not an ever-growing buffer. Every `feed` call is also
+/// fail-fast: a lead byte followed by anything other than a valid
+/// continuation byte is rejected on the call where the mismatch appears,
+/// rather than being buffered in the hope that it resolves later.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Utf8Validator {
+    state: Utf8State,
+    /// Total number of bytes passed to `feed` so far, used to translate
+    /// the per-call, locally-scoped error offsets from
+    /// [`validate_utf8_resumable`] into offsets in the whole stream.
+    stream_pos: usize,
+}
+
+#[cfg(feature = "std")]
+impl Utf8Validator {
+    /// Creates a validator with no pending partial sequence.
+    pub const fn new() -> Self {
+        Self { state: Utf8State::Complete, stream_pos: 0 }
+    }
+
+    fn buffered_len(&self) -> usize {
+        match self.state {
+            Utf8State::Complete => 0,
+            Utf8State::NeedMore { buffered } => buffered.len as usize,
+        }
+    }
+
+    /// Validates the next `chunk` of the stream.
+    ///
+    /// Returns `Ok(())` if `chunk` is valid so far, including the case
+    /// where it ends mid-sequence and the remainder is now buffered
+    /// waiting for a later `feed` call. Returns `Err` the moment a byte is
+    /// seen that cannot begin or continue a valid UTF-8 sequence; the
+    /// error's `valid_up_to` is an offset into the whole stream fed so
+    /// far, not just `chunk`. Once `feed` returns `Err`, the validator
+    /// should be discarded: it does not attempt to resynchronize.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<(), Utf8Error> {
+        let local_start = self.stream_pos - self.buffered_len();
+        let (state, result) = validate_utf8_resumable(chunk, self.state);
+        self.stream_pos += chunk.len();
+
+        match result {
+            Ok(()) => {
+                self.state = state;
+                Ok(())
+            }
+            Err(mut err) => {
+                err.valid_up_to += local_start;
+                Err(err)
+            }
+        }
+    }
+
+    /// `true` if there is no partial sequence awaiting more bytes, i.e.
+    /// every byte fed so far belongs to a complete character.
+    pub fn is_complete(&self) -> bool {
+        matches!(self.state, Utf8State::Complete)
+    }
+
+    /// Signals that no more data is coming, and errors if a character was
+    /// left incomplete by the last `feed` call.
+    ///
+    /// A dangling lead byte is not an error on its own — more bytes might
+    /// still arrive — but once the caller knows the stream has ended, it
+    /// becomes one, at the offset where the incomplete character started.
+    pub fn finish(&self) -> Result<(), Utf8Error> {
+        if self.is_complete() {
+            Ok(())
+        } else {
+            Err(Utf8Error {
+                valid_up_to: self.stream_pos - self.buffered_len(),
+                error_len: None,
+                error_byte: None,
+            })
+        }
+    }
+}
+
+/// Validates a sequence of `parts` as one logical, concatenated UTF-8
+/// buffer, without copying them into a single contiguous buffer first.
+///
+/// This is the natural use case for [`Utf8Validator`]: each part is fed
+/// in turn, carrying at most 3 bytes of any character that straddles a
+/// part boundary. Errors are reported at their offset in the
+/// concatenated coordinate space, i.e. as if `parts` had been copied into
+/// one buffer first. Useful for `readv`-style I/O that hands back
+/// scattered, logically-contiguous regions.
+#[cfg(feature = "std")]
+pub fn validate_utf8_slices(parts: &[&[u8]]) -> Result<(), Utf8Error> {
+    let mut validator = Utf8Validator::new();
+    for part in parts {
+        validator.feed(part)?;
+    }
+    validator.finish()
+}
+
+/// Validates the buffers filled by a vectored read (e.g.
+/// `std::io::Read::read_vectored`'s `IoSliceMut`s, once borrowed as plain
+/// `&[u8]`s) as one logical, concatenated UTF-8 stream, without copying
+/// them into a single contiguous buffer first.
+///
+/// This is [`validate_utf8_slices`] under the name callers reaching for
+/// scatter-gather I/O will look for; the two are otherwise identical,
+/// including `valid_up_to` being an absolute offset into the logical
+/// concatenation, not into whichever slice it falls in.
+#[cfg(feature = "std")]
+pub fn validate_utf8_vectored(slices: &[&[u8]]) -> Result<(), Utf8Error> {
+    validate_utf8_slices(slices)
+}
+
+/// Validates a full [`Read`] stream as UTF-8 without first reading it
+/// entirely into memory: bytes are pulled in fixed-size chunks and fed
+/// through a [`Utf8Validator`], which carries any partial trailing
+/// sequence across reads.
+///
+/// The outer `io::Result` reports I/O failures from `reader`; the inner
+/// `Result<(), Utf8Error>` reports the first invalid byte, with
+/// `valid_up_to` relative to the whole stream read so far, not just the
+/// chunk it was found in.
+#[cfg(feature = "std")]
+pub fn validate_utf8_from_reader<R: Read>(mut reader: R) -> std::io::Result<Result<(), Utf8Error>> {
+    let mut chunk = [0u8; 8192];
+    let mut validator = Utf8Validator::new();
+
+    loop {
+        let n = reader.read(&mut chunk)?;
+        if n == 0 {
+            break;
+        }
+        if let Err(err) = validator.feed(&chunk[..n]) {
+            return Ok(Err(err));
+        }
+    }
+
+    Ok(validator.finish())
+}
+
+/// External state for [`validate_utf8_with_checkpoint`]: cumulative bytes
+/// validated across all calls so far, plus any partial trailing sequence
+/// carried into the next one.
+///
+/// The free-function equivalent of [`Utf8Validator`], for callback-based
+/// I/O loops (e.g. a socket read callback) that get handed a buffer and a
+/// place to stash state, rather than owning a validator object across
+/// calls.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Checkpoint {
+    state: Utf8State,
+    /// Total number of bytes passed to [`validate_utf8_with_checkpoint`]
+    /// across all calls so far.
+    pub total_bytes: usize,
+}
+
+#[cfg(feature = "std")]
+impl Checkpoint {
+    /// Creates a checkpoint at the start of a stream, with no bytes
+    /// validated yet and no partial sequence pending.
+    pub const fn new() -> Self {
+        Self { state: Utf8State::Complete, total_bytes: 0 }
+    }
+
+    /// `true` if there is no partial sequence awaiting more bytes.
+    pub fn is_complete(&self) -> bool {
+        matches!(self.state, Utf8State::Complete)
+    }
+
+    fn buffered_len(&self) -> usize {
+        match self.state {
+            Utf8State::Complete => 0,
+            Utf8State::NeedMore { buffered } => buffered.len as usize,
+        }
+    }
+}
+
+/// Validates the next `buf` of a stream against `cp`, updating it in
+/// place for the next call.
+///
+/// On error, `Utf8Error::valid_up_to` is an absolute offset into the
+/// whole stream (using `cp.total_bytes` from before this call), not just
+/// `buf`. See [`Utf8Validator::feed`] for the equivalent object-oriented
+/// API; this free-function form suits call sites where the caller
+/// already owns the state and just needs to thread it through.
+#[cfg(feature = "std")]
+pub fn validate_utf8_with_checkpoint(buf: &[u8], cp: &mut Checkpoint) -> Result<(), Utf8Error> {
+    let local_start = cp.total_bytes - cp.buffered_len();
+    let (state, result) = validate_utf8_resumable(buf, cp.state);
+    cp.total_bytes += buf.len();
+
+    match result {
+        Ok(()) => {
+            cp.state = state;
+            Ok(())
+        }
+        Err(mut err) => {
+            err.valid_up_to += local_start;
+            Err(err)
+        }
+    }
+}
+
+/// True if `ch` is a Unicode combining mark, for the handful of blocks
+/// covered by [`validate_utf8_count_combining`]'s cheap width heuristic:
+/// Combining Diacritical Marks (U+0300..=U+036F), Combining Diacritical
+/// Marks Extended (U+1AB0..=U+1AFF), Combining Diacritical Marks
+/// Supplement (U+1DC0..=U+1DFF), Combining Diacritical Marks for Symbols
+/// (U+20D0..=U+20FF), and Combining Half Marks (U+FE20..=U+FE2F).
+///
+/// This is not a full Unicode `Mn`/`Mc`/`Me` classification — it covers
+/// the blocks a Latin/Cyrillic/Greek-heavy terminal is actually likely to
+/// see, not every combining character in Unicode.
+const fn is_combining_mark(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x0300..=0x036F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE20..=0xFE2F
+    )
+}
+
+/// Validates `buf` as UTF-8 and counts how many of its characters are
+/// combining marks (see `is_combining_mark`).
+///
+/// Combining marks attach to the preceding base character without adding
+/// to a terminal's or UI's display width, so a cheap display-width
+/// estimate can start from `char_count - combining_count` rather than
+/// pulling in a full grapheme-segmentation library.
+pub fn validate_utf8_count_combining(buf: &[u8]) -> Result<usize, Utf8Error> {
+    validate_utf8(buf)?;
+
+    // SAFETY: `buf` was just validated as UTF-8
+    let s = unsafe { str::from_utf8_unchecked(buf) };
+
+    Ok(s.chars().filter(|&ch| is_combining_mark(ch)).count())
+}
+
+/// A byte's role in the UTF-8 encoding of the character it belongs to, as
+/// written by [`validate_and_categorize`].
+pub const CATEGORY_ASCII: u8 = 0;
+/// See [`CATEGORY_ASCII`].
+pub const CATEGORY_LEAD: u8 = 1;
+/// See [`CATEGORY_ASCII`].
+pub const CATEGORY_CONT: u8 = 2;
+
+/// Validates `buf` as UTF-8 and fills `out` with a per-byte category code
+/// ([`CATEGORY_ASCII`], [`CATEGORY_LEAD`], or [`CATEGORY_CONT`]), so a
+/// syntax highlighter can classify every offset without a second pass
+/// over the buffer.
+///
+/// Runs of ASCII bytes are classified with a single slice `fill` rather
+/// than one write per byte, the same run-collapsing idea as
+/// [`validate_utf8_char_boundaries`] applied to a flat bitmap instead of
+/// a `Vec` of ranges.
+///
+/// # Panics
+///
+/// Panics if `out.len() != buf.len()`.
+pub fn validate_and_categorize(buf: &[u8], out: &mut [u8]) -> Result<(), Utf8Error> {
+    assert_eq!(out.len(), buf.len(), "`out` must be the same length as `buf`");
+
+    validate_utf8(buf)?;
+
+    let mut curr = 0;
+    while curr < buf.len() {
+        let width = utf8_char_width(buf[curr]);
+        if width == 1 {
+            let start = curr;
+            while curr < buf.len() && utf8_char_width(buf[curr]) == 1 {
+                curr += 1;
+            }
+            out[start..curr].fill(CATEGORY_ASCII);
+        } else {
+            out[curr] = CATEGORY_LEAD;
+            out[curr + 1..curr + width].fill(CATEGORY_CONT);
+            curr += width;
+        }
+    }
+
+    Ok(())
+}
+
+/// Internal abstraction over a fixed-width "vector" of bytes loaded from a
+/// buffer, so that a new backend (a new ISA, or simply a different word
+/// count) can be added by implementing this trait once, instead of
+/// duplicating the scan loop, tail handling, and hand-off to
+/// [`validate_non_acii_bytes`] for every variant.
+///
+/// The portable `[usize; N]` implementation below is what the existing
+/// 8x/2x block loops are built on; hand-written ISA backends (SSE2, AVX2,
+/// NEON, ...) are expected to implement this trait for their own native
+/// vector type.
+pub(crate) trait ByteVector: Copy {
+    /// Loads a vector's worth of bytes starting at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads of `size_of::<Self>()` bytes.
+    unsafe fn load(ptr: *const u8) -> Self;
+
+    /// `true` if any lane holds a byte with its most significant bit set,
+    /// i.e. any non-ASCII byte is present.
+    fn any_high_bit(self) -> bool;
+}
+
+impl<const N: usize> ByteVector for [usize; N] {
+    #[inline(always)]
+    unsafe fn load(ptr: *const u8) -> Self {
+        // SAFETY: forwarded to the caller of `ByteVector::load`
+        unsafe { *(ptr as *const Self) }
+    }
+
+    #[inline(always)]
+    fn any_high_bit(self) -> bool {
+        has_non_ascii_byte(&self)
+    }
+}
+
+/// Runtime override forcing the portable scalar/SWAR backend, for
+/// environments where `is_x86_feature_detected!` reports a SIMD ISA that
+/// then faults in practice (a known quirk of some virtualized sandboxes).
+///
+/// [`validate_utf8`] and [`validate_utf8_auto`] read this flag on every
+/// call before taking an AVX2/NEON/simd128 path (under the `simd`
+/// feature), and [`dispatch`] reads it when it first resolves a backend.
+static FORCE_SCALAR_BACKEND_FLAG: core::sync::atomic::AtomicBool =
+    core::sync::atomic::AtomicBool::new(false);
+
+/// Forces all future backend-dispatch decisions in this process to pick
+/// the portable scalar/SWAR backend. A backend [`dispatch`] has already
+/// resolved and cached is not replaced.
+///
+/// # Ordering
+///
+/// The override is stored with [`core::sync::atomic::Ordering::SeqCst`], and a dispatcher is
+/// expected to read it with the same ordering, so calling this before
+/// any validation call is guaranteed to be observed by every thread's
+/// first dispatch decision. Calling it concurrently with in-flight
+/// validation only guarantees that *subsequent* dispatch decisions see
+/// the override, not calls already past their own dispatch point.
+pub fn force_scalar_backend() {
+    FORCE_SCALAR_BACKEND_FLAG.store(true, core::sync::atomic::Ordering::SeqCst);
+}
+
+/// `true` if [`force_scalar_backend`] has been called in this process.
+pub fn scalar_backend_forced() -> bool {
+    FORCE_SCALAR_BACKEND_FLAG.load(core::sync::atomic::Ordering::SeqCst)
+}
+
+/// Function pointer type for a resolved UTF-8 validation backend, as cached
+/// by [`dispatch`].
+type ValidateFn = fn(&[u8]) -> Result<(), Utf8Error>;
+
+/// Caches the backend resolved by [`resolve_backend`], ifunc-style: `0`
+/// means "not yet resolved", any other value is a [`ValidateFn`] cast to
+/// `usize`. `AtomicUsize` is used purely as storage for the pointer bits;
+/// a `fn` and a `usize` are the same width on every target this crate
+/// supports, so the round trip through [`dispatch`] never reinterprets
+/// unrelated data.
+static DISPATCH_CACHE: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+
+/// Numeric codes [`resolve_backend`] pairs with its chosen [`ValidateFn`],
+/// so [`dispatch`] can cache which backend it picked (for
+/// [`active_backend`]) alongside the pointer to call, without storing a
+/// `&'static str` (a fat pointer) in an atomic.
+const BACKEND_SCALAR: u8 = 0;
+const BACKEND_AVX2: u8 = 1;
+const BACKEND_NEON: u8 = 2;
+const BACKEND_SIMD128: u8 = 3;
+
+/// Caches the [`BACKEND_*`](BACKEND_SCALAR) code [`resolve_backend`] chose,
+/// for [`active_backend`] to report without re-running the
+/// feature-detection cascade. Only meaningful once [`DISPATCH_CACHE`] has
+/// actually been resolved; defaults to [`BACKEND_SCALAR`] until then, same
+/// as an unresolved [`dispatch`] would fall back to.
+static ACTIVE_BACKEND: core::sync::atomic::AtomicU8 = core::sync::atomic::AtomicU8::new(BACKEND_SCALAR);
+
+/// Runs the same `is_x86_feature_detected!`/`is_aarch64_feature_detected!`
+/// cascade [`validate_utf8`] runs inline, but returns the chosen backend as
+/// a function pointer (paired with its [`BACKEND_*`](BACKEND_SCALAR) code)
+/// instead of calling it directly, so [`dispatch`] can cache the decision
+/// instead of repeating it on every call.
+fn resolve_backend() -> (ValidateFn, u8) {
+    #[cfg(all(feature = "simd", feature = "std", target_arch = "x86_64"))]
+    {
+        if !scalar_backend_forced() && is_x86_feature_detected!("avx2") {
+            return (validate_utf8_avx2, BACKEND_AVX2);
+        }
+    }
+
+    #[cfg(all(feature = "simd", feature = "std", target_arch = "aarch64"))]
+    {
+        if !scalar_backend_forced() && is_aarch64_feature_detected!("neon") {
+            return (validate_utf8_neon, BACKEND_NEON);
+        }
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))]
+    {
+        if !scalar_backend_forced() {
+            return (validate_utf8_simd128, BACKEND_SIMD128);
+        }
+    }
+
+    (scalar_backend_fallback, BACKEND_SCALAR)
+}
+
+/// The portable backend [`resolve_backend`] falls back to when no ISA
+/// backend is compiled in, available, or [`scalar_backend_forced`] has
+/// overridden the choice. A free function (rather than a closure) so it
+/// coerces to the same [`ValidateFn`] as the ISA backends.
+fn scalar_backend_fallback(buf: &[u8]) -> Result<(), Utf8Error> {
+    validate_utf8_with_stats(buf, None)
+}
+
+/// Resolves the best available backend once per process and caches the
+/// function pointer in an [`AtomicUsize`](core::sync::atomic::AtomicUsize),
+/// ifunc-style. The first call pays for the feature-detection cascade
+/// [`validate_utf8`] itself runs inline on every call; every call after
+/// that just loads the cached pointer with
+/// [`Ordering::Relaxed`](core::sync::atomic::Ordering::Relaxed), since the
+/// only thing being synchronized is which backend to jump to, and a
+/// backend function's own code is already visible to every thread once
+/// the program is running.
+///
+/// [`force_scalar_backend`] only affects the choice if it is set before
+/// the first call resolves and caches a backend; unlike [`validate_utf8`]'s
+/// own inline cascade, which reads the flag on every call, a decision
+/// already cached before the override was set is not invalidated.
+pub fn dispatch() -> ValidateFn {
+    let cached = DISPATCH_CACHE.load(core::sync::atomic::Ordering::Relaxed);
+    if cached != 0 {
+        return unsafe { core::mem::transmute::<usize, ValidateFn>(cached) };
+    }
+
+    let (resolved, code) = resolve_backend();
+    ACTIVE_BACKEND.store(code, core::sync::atomic::Ordering::Relaxed);
+    DISPATCH_CACHE.store(resolved as usize, core::sync::atomic::Ordering::Relaxed);
+    resolved
+}
+
+/// Reports which backend [`dispatch`] has resolved and cached for this
+/// process, as `"avx2"`, `"neon"`, `"simd128"`, or `"scalar"` — useful for
+/// benchmarks and diagnostics that want to record which path actually ran
+/// rather than just which ones were compiled in. Resolves via [`dispatch`]
+/// first if no call has been made yet, so the answer always reflects a
+/// real decision rather than the `BACKEND_SCALAR` default.
+pub fn active_backend() -> &'static str {
+    dispatch();
+
+    match ACTIVE_BACKEND.load(core::sync::atomic::Ordering::Relaxed) {
+        BACKEND_AVX2 => "avx2",
+        BACKEND_NEON => "neon",
+        BACKEND_SIMD128 => "simd128",
+        _ => "scalar",
+    }
+}
+
+/// Validates `buf` using the backend [`dispatch`] resolves and caches,
+/// instead of [`validate_utf8`]'s own inline (uncached) feature-detection
+/// cascade. Prefer this over [`validate_utf8`] in hot loops that call into
+/// the crate many times per process, where repeating
+/// `is_x86_feature_detected!` on every call would otherwise be wasted
+/// work; for a single one-off call, [`validate_utf8`] is simpler and no
+/// slower.
+pub fn validate_utf8_dispatched(buf: &[u8]) -> Result<(), Utf8Error> {
+    dispatch()(buf)
+}
+
+/// AVX2-accelerated ASCII fast-path scan for `x86_64`, dispatched into by
+/// [`validate_utf8`] at runtime (behind the `simd` feature, which also
+/// needs `std` for `is_x86_feature_detected!`) when
+/// `is_x86_feature_detected!("avx2")` reports the CPU actually supports
+/// it and [`scalar_backend_forced`] hasn't overridden that. Falls back to
+/// the same [`validate_non_acii_bytes`] used by every other variant once
+/// a non-ASCII byte is found, so error positions are byte-identical to
+/// the portable path.
+#[cfg(all(feature = "simd", feature = "std", target_arch = "x86_64"))]
+mod simd_x86 {
+    use core::arch::x86_64::{__m256i, _mm256_loadu_si256, _mm256_movemask_epi8};
+
+    /// Width, in bytes, of one AVX2 lane.
+    pub(super) const LANE: usize = 32;
+
+    /// Returns the offset of the first non-ASCII byte in the 32-byte lane
+    /// starting at `ptr`, or `None` if the whole lane is ASCII.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads of [`LANE`] bytes, and the AVX2
+    /// target feature must be available on the current CPU.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn first_non_ascii(ptr: *const u8) -> Option<u32> {
+        // SAFETY: forwarded to the caller of `first_non_ascii`
+        let vector = unsafe { _mm256_loadu_si256(ptr as *const __m256i) };
+        // one bit per lane, set exactly where that byte's high bit is set
+        let mask = _mm256_movemask_epi8(vector) as u32;
+        if mask == 0 {
+            None
+        } else {
+            Some(mask.trailing_zeros())
+        }
+    }
+}
+
+/// See [`simd_x86`]. Mirrors [`validate_utf8_with_stats`]'s ASCII scan,
+/// but finds the first non-ASCII byte in each 32-byte lane with
+/// [`_mm256_movemask_epi8`](core::arch::x86_64::_mm256_movemask_epi8)
+/// instead of the portable word-block masking.
+#[cfg(all(feature = "simd", feature = "std", target_arch = "x86_64"))]
+fn validate_utf8_avx2(buf: &[u8]) -> Result<(), Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+    let lanes_end = block_end(end, simd_x86::LANE);
+
+    while curr < end {
+        if buf[curr] < 128 {
+            if curr >= lanes_end {
+                curr += 1;
+                continue;
+            }
+
+            // SAFETY: `curr < lanes_end` guarantees `LANE` readable bytes
+            // remain, and this function is only reached once AVX2 has
+            // been confirmed available on the current CPU.
+            match unsafe { simd_x86::first_non_ascii(buf.as_ptr().add(curr)) } {
+                None => {
+                    curr += simd_x86::LANE;
+                    continue;
+                }
+                Some(offset) => curr += offset as usize,
+            }
+        }
+
+        match validate_non_acii_bytes(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+/// NEON-accelerated ASCII fast-path scan for `aarch64`, dispatched into by
+/// [`validate_utf8`] at runtime (behind the `simd` feature, which also
+/// needs `std` for `is_aarch64_feature_detected!`) when
+/// `is_aarch64_feature_detected!("neon")` reports the CPU actually
+/// supports it and [`scalar_backend_forced`] hasn't overridden that.
+///
+/// Unlike AVX2's `_mm256_movemask_epi8`, NEON has no cheap byte-mask
+/// instruction, so this reuses the exact same masked-word representation
+/// (only the MSB of each byte set) that the portable 8x/2x block loop
+/// produces, and hands it to the same [`non_ascii_byte_position`] to find
+/// the exact offending byte — the "integrate with the existing
+/// `non_ascii_byte_position` logic" this backend is built around.
+#[cfg(all(feature = "simd", feature = "std", target_arch = "aarch64"))]
+mod simd_neon {
+    use core::arch::aarch64::{uint8x16_t, vandq_u8, vdupq_n_u8, vld1q_u8, vmaxvq_u8, vst1q_u8};
+
+    /// Width, in bytes, of one NEON lane.
+    pub(super) const LANE: usize = 16;
+
+    /// Returns the offset of the first non-ASCII byte in the 16-byte lane
+    /// starting at `ptr`, or `None` if the whole lane is ASCII.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads of [`LANE`] bytes, and the NEON
+    /// target feature must be available on the current CPU.
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn first_non_ascii(ptr: *const u8) -> Option<u32> {
+        // SAFETY: forwarded to the caller of `first_non_ascii`
+        let vector: uint8x16_t = unsafe { vld1q_u8(ptr) };
+
+        // `vmaxvq_u8` is a cheap horizontal max: if it stays <= 0x7F, every
+        // lane is ASCII and there's no need to build the masked word below.
+        if unsafe { vmaxvq_u8(vector) } <= 0x7F {
+            return None;
+        }
+
+        // mask down to just the MSB of each byte, exactly like the
+        // portable path's `mask_block`, so the two 8-byte halves can be
+        // handed to `non_ascii_byte_position` unchanged
+        let masked = unsafe { vandq_u8(vector, vdupq_n_u8(0x80)) };
+        let mut bytes = [0u8; LANE];
+        unsafe { vst1q_u8(bytes.as_mut_ptr(), masked) };
+
+        let words: [usize; 2] = [
+            usize::from_ne_bytes(bytes[..8].try_into().unwrap()),
+            usize::from_ne_bytes(bytes[8..].try_into().unwrap()),
+        ];
+
+        // SAFETY: `vmaxvq_u8` above guarantees at least one non-ASCII byte
+        Some(unsafe { super::non_ascii_byte_position(&words) })
+    }
+}
+
+/// See [`simd_neon`]. Mirrors [`validate_utf8_with_stats`]'s ASCII scan,
+/// but finds the first non-ASCII byte in each 16-byte lane with NEON's
+/// `vmaxvq_u8`/`vandq_u8` instead of the portable word-block masking.
+#[cfg(all(feature = "simd", feature = "std", target_arch = "aarch64"))]
+fn validate_utf8_neon(buf: &[u8]) -> Result<(), Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+    let lanes_end = block_end(end, simd_neon::LANE);
+
+    while curr < end {
+        if buf[curr] < 128 {
+            if curr >= lanes_end {
+                curr += 1;
+                continue;
+            }
+
+            // SAFETY: `curr < lanes_end` guarantees `LANE` readable bytes
+            // remain, and this function is only reached once NEON has
+            // been confirmed available on the current CPU.
+            match unsafe { simd_neon::first_non_ascii(buf.as_ptr().add(curr)) } {
+                None => {
+                    curr += simd_neon::LANE;
+                    continue;
+                }
+                Some(offset) => curr += offset as usize,
+            }
+        }
+
+        match validate_non_acii_bytes(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+/// `wasm32` `simd128` specialization of the ASCII fast-path scan,
+/// dispatched into by [`validate_utf8`] at runtime (behind the `simd`
+/// feature) for Wasm runtimes/toolchains that were built with the
+/// `simd128` target feature enabled (e.g. via
+/// `RUSTFLAGS="-C target-feature=+simd128"`). Unlike the x86_64/aarch64
+/// backends, `simd128` has no runtime feature-detection story of its own
+/// (the target feature is either compiled in or it isn't), so this is
+/// gated at compile time on `target_feature = "simd128"` rather than an
+/// `is_*_feature_detected!` check.
+#[cfg(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))]
+mod simd_wasm32 {
+    use core::arch::wasm32::{u8x16_gt, u8x16_splat, v128, v128_and, v128_any_true, v128_load, v128_store};
+
+    /// Width, in bytes, of one `simd128` lane.
+    pub(super) const LANE: usize = 16;
+
+    /// Returns the offset of the first non-ASCII byte in the 16-byte lane
+    /// starting at `ptr`, or `None` if the whole lane is ASCII.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads of [`LANE`] bytes.
+    pub(super) unsafe fn first_non_ascii(ptr: *const u8) -> Option<u32> {
+        // SAFETY: forwarded to the caller of `first_non_ascii`
+        let vector: v128 = unsafe { v128_load(ptr as *const v128) };
+
+        // one lane set to 0xFF per byte greater than 0x7F, 0x00 otherwise
+        let above_ascii = u8x16_gt(vector, u8x16_splat(0x7F));
+        if !v128_any_true(above_ascii) {
+            return None;
+        }
+
+        // mask down to just the MSB of each byte, exactly like the
+        // portable path's `mask_block`, so the two 8-byte halves can be
+        // handed to `non_ascii_byte_position` unchanged
+        let masked = v128_and(above_ascii, u8x16_splat(0x80));
+        let mut bytes = [0u8; LANE];
+        // SAFETY: `bytes` is a local array of exactly `LANE` bytes
+        unsafe { v128_store(bytes.as_mut_ptr() as *mut v128, masked) };
+
+        let words: [usize; 2] = [
+            usize::from_ne_bytes(bytes[..8].try_into().unwrap()),
+            usize::from_ne_bytes(bytes[8..].try_into().unwrap()),
+        ];
+
+        // SAFETY: `v128_any_true` above guarantees at least one non-ASCII byte
+        Some(unsafe { super::non_ascii_byte_position(&words) })
+    }
+}
+
+/// See [`simd_wasm32`]. Mirrors [`validate_utf8_with_stats`]'s ASCII scan,
+/// but finds the first non-ASCII byte in each 16-byte lane with
+/// `simd128`'s `u8x16_gt` instead of the portable word-block masking.
+#[cfg(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))]
+fn validate_utf8_simd128(buf: &[u8]) -> Result<(), Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+    let lanes_end = block_end(end, simd_wasm32::LANE);
+
+    while curr < end {
+        if buf[curr] < 128 {
+            if curr >= lanes_end {
+                curr += 1;
+                continue;
+            }
+
+            // SAFETY: `curr < lanes_end` guarantees `LANE` readable bytes remain
+            match unsafe { simd_wasm32::first_non_ascii(buf.as_ptr().add(curr)) } {
+                None => {
+                    curr += simd_wasm32::LANE;
+                    continue;
+                }
+                Some(offset) => curr += offset as usize,
+            }
+        }
+
+        match validate_non_acii_bytes(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates `buf` and returns it as a `&str` with no copy when it is
+/// already clean UTF-8, or falls back to an owned, cleaned `String` when
+/// the only problem is a leading BOM or an incomplete trailing sequence.
+///
+/// A leading UTF-8 BOM (`EF BB BF`) is stripped. A truncated final
+/// character (`error_len: None`) is trimmed off, keeping the valid
+/// prefix. Any other error is a genuine structural problem and is
+/// returned as `Err`.
+#[cfg(feature = "std")]
+pub fn normalize_utf8(buf: &[u8]) -> Result<Cow<'_, str>, Utf8Error> {
+    match validate_utf8(buf) {
+        Ok(()) if buf.starts_with(&[0xEF, 0xBB, 0xBF]) => {
+            // SAFETY: `buf` was just validated as UTF-8 and the BOM is a
+            // whole, self-contained character, so the remainder is too
+            let stripped = unsafe { str::from_utf8_unchecked(&buf[3..]) };
+            Ok(Cow::Owned(stripped.to_owned()))
+        }
+        Ok(()) => {
+            // SAFETY: `buf` was just validated as UTF-8
+            Ok(Cow::Borrowed(unsafe { str::from_utf8_unchecked(buf) }))
+        }
+        Err(e) if e.error_len.is_none() => {
+            let valid = &buf[..e.valid_up_to];
+            let valid = valid.strip_prefix(&[0xEF, 0xBB, 0xBF][..]).unwrap_or(valid);
+            // SAFETY: `valid_up_to` is guaranteed to be a valid UTF-8
+            // boundary, and the BOM is a whole, self-contained character
+            let prefix = unsafe { str::from_utf8_unchecked(valid) };
+            Ok(Cow::Owned(prefix.to_owned()))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Computes the *plan* [`String::from_utf8_lossy`] would carry out on
+/// `buf`, without allocating the replaced string itself: the byte range
+/// of each invalid subsequence, paired with the [`char::REPLACEMENT_CHARACTER`]
+/// it would become.
+///
+/// Repeatedly re-runs [`validate_utf8`] on the remainder of `buf` after
+/// each error, using the returned `error_len` (or its absence, for a
+/// truncated trailing sequence) to decide how many bytes one U+FFFD
+/// covers before resuming — the same grouping rule
+/// [`Utf8Error::error_len`] documents as mirroring
+/// `core::str::Utf8Error`. A caller can render "these `n` regions will
+/// become U+FFFD" as a lossy-repair preview and let the user confirm
+/// before actually producing the repaired string.
+#[cfg(feature = "std")]
+pub fn validate_utf8_returning_replacement_plan(buf: &[u8]) -> Vec<(Range<usize>, char)> {
+    let mut plan = Vec::new();
+    let mut pos = 0;
+
+    while pos < buf.len() {
+        match validate_utf8(&buf[pos..]) {
+            Ok(()) => break,
+            Err(e) => {
+                let start = pos + e.valid_up_to;
+                match e.error_len {
+                    Some(len) => {
+                        let end = start + len as usize;
+                        plan.push((start..end, char::REPLACEMENT_CHARACTER));
+                        pos = end;
+                    }
+                    None => {
+                        plan.push((start..buf.len(), char::REPLACEMENT_CHARACTER));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    plan
+}
+
+/// Iterator over the valid UTF-8 runs in a buffer that may have several
+/// invalid regions scattered through it, as returned by
+/// [`ValidChunks::new`]. Each item is the longest valid `&str` run found
+/// before the next error; the invalid bytes between runs are skipped
+/// rather than yielded.
+///
+/// After an error, [`Utf8Error::error_len`] bytes (or, when `None`, just
+/// the one byte at [`Utf8Error::valid_up_to`]) are skipped before resuming
+/// the scan; unlike [`validate_utf8_returning_replacement_plan`], a
+/// truncated tail is not skipped as a whole.
+pub struct ValidChunks<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ValidChunks<'a> {
+    /// Creates an iterator over `buf`'s valid UTF-8 runs.
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for ValidChunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        while self.pos < self.buf.len() {
+            match validate_utf8(&self.buf[self.pos..]) {
+                Ok(()) => {
+                    let start = self.pos;
+                    self.pos = self.buf.len();
+                    // SAFETY: `validate_utf8` confirmed `buf[start..]` is
+                    // valid UTF-8
+                    return Some(unsafe { str::from_utf8_unchecked(&self.buf[start..]) });
+                }
+                Err(e) => {
+                    let start = self.pos;
+                    let valid_end = start + e.valid_up_to;
+                    self.pos = valid_end + e.error_len.map_or(1, |len| len as usize);
+
+                    if valid_end > start {
+                        // SAFETY: `validate_utf8` confirmed
+                        // `buf[start..valid_end]` is valid UTF-8
+                        return Some(unsafe {
+                            str::from_utf8_unchecked(&self.buf[start..valid_end])
+                        });
+                    }
+
+                    // no valid bytes before this error; skip it and keep scanning
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Counts how many `U+FFFD` replacement characters [`from_utf8_lossy`]
+/// (or `String::from_utf8_lossy`) would insert when lossily decoding
+/// `buf`, without allocating the replaced string itself.
+///
+/// Walks the same maximal-invalid-subsequence grouping as
+/// [`validate_utf8_returning_replacement_plan`] — one U+FFFD per group,
+/// matching std's grouping rules exactly — so a caller can pre-size an
+/// output `String` (`buf.len() + count_invalid_sequences(buf) * 2`, since
+/// U+FFFD is 3 bytes in UTF-8 versus at least 1 for whatever it replaces)
+/// before doing the actual lossy decode.
+pub fn count_invalid_sequences(buf: &[u8]) -> usize {
+    let mut count = 0;
+    let mut pos = 0;
+
+    while pos < buf.len() {
+        match validate_utf8(&buf[pos..]) {
+            Ok(()) => break,
+            Err(e) => {
+                count += 1;
+                match e.error_len {
+                    Some(len) => pos += e.valid_up_to + len as usize,
+                    None => break,
+                }
+            }
+        }
+    }
+
+    count
+}
+
+/// Validates `buf` as UTF-8 and returns it as a `Cow<str>`, replacing
+/// each maximal invalid subsequence with one U+FFFD — the `Cow`
+/// equivalent of [`String::from_utf8_lossy`], driven by this crate's
+/// faster [`validate_utf8`] scan instead of the standard library's.
+///
+/// Gated behind `std` rather than a separate `alloc` feature, consistent
+/// with every other `Cow`/`String`-returning API in this crate (e.g.
+/// [`normalize_utf8`]) — this crate doesn't otherwise distinguish `alloc`
+/// from `std`, so adding a one-off `alloc`-only feature just for this
+/// function would be a distinction without a difference here.
+///
+/// Returns `Cow::Borrowed` untouched when `buf` is already wholly valid;
+/// otherwise walks the same `(range, replacement)` plan
+/// [`validate_utf8_returning_replacement_plan`] computes — one
+/// replacement per maximal invalid subsequence, matching std's grouping
+/// — and builds an owned, repaired `String`.
+#[cfg(feature = "std")]
+pub fn from_utf8_lossy(buf: &[u8]) -> Cow<'_, str> {
+    let plan = validate_utf8_returning_replacement_plan(buf);
+    if plan.is_empty() {
+        // SAFETY: an empty plan means `validate_utf8` reported no error
+        return Cow::Borrowed(unsafe { str::from_utf8_unchecked(buf) });
+    }
+
+    let mut out = String::with_capacity(buf.len());
+    let mut pos = 0;
+    for (range, replacement) in plan {
+        // SAFETY: `pos..range.start` is either the buffer's start or the
+        // tail of a previous, already-validated-or-replaced region
+        out.push_str(unsafe { str::from_utf8_unchecked(&buf[pos..range.start]) });
+        out.push(replacement);
+        pos = range.end;
+    }
+    // SAFETY: the trailing bytes past the last replacement were validated
+    // as UTF-8 by `validate_utf8_returning_replacement_plan`'s final scan
+    out.push_str(unsafe { str::from_utf8_unchecked(&buf[pos..]) });
+
+    Cow::Owned(out)
+}
+
+/// Interprets `buf` as UTF-8 if it validates, or as Latin-1 (ISO-8859-1)
+/// otherwise, returning an owned `String` either way.
+///
+/// Latin-1 maps every byte directly to the Unicode scalar value of the
+/// same number (`U+0000..=U+00FF`), so unlike [`from_utf8_lossy`] this
+/// never loses information or introduces `U+FFFD`: any byte sequence has
+/// exactly one Latin-1 reading. Useful for text of unknown provenance
+/// that is either UTF-8 or a legacy single-byte encoding, where a
+/// mis-detected encoding is worse than the extra validation pass this
+/// function performs.
+///
+/// Gated behind `std` rather than a separate `alloc` feature, matching
+/// every other `String`-returning API in this crate (e.g.
+/// [`from_utf8_lossy`]).
+#[cfg(feature = "std")]
+pub fn utf8_or_latin1_to_string(buf: &[u8]) -> String {
+    match validate_utf8(buf) {
+        // SAFETY: `buf` was just validated as UTF-8
+        Ok(()) => unsafe { str::from_utf8_unchecked(buf) }.to_owned(),
+        Err(_) => buf.iter().map(|&b| b as char).collect(),
+    }
+}
+
+/// A coarse classification of why a UTF-8 sequence was rejected, for
+/// [`error_kind_histogram`]'s corpus-wide corruption audit.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Utf8ErrorKind {
+    /// The buffer ended before a full sequence could be read.
+    Truncated,
+    /// A continuation byte (`0x80..=0xBF`) appeared where a lead byte was
+    /// expected.
+    StrayContinuation,
+    /// A lead byte that can never start a valid sequence: `0xC0`, `0xC1`,
+    /// or `0xF5..=0xFF`.
+    InvalidLead,
+    /// A sequence that encodes a scalar value which has a shorter valid
+    /// encoding (e.g. `E0 80 80`), rejected per RFC 3629 even though the
+    /// bit pattern alone would decode to a real scalar.
+    Overlong,
+    /// A sequence that encodes one of the UTF-16 surrogate halves
+    /// (U+D800..=U+DFFF), which are not valid scalar values.
+    Surrogate,
+    /// A sequence that encodes a scalar value above U+10FFFF.
+    OutOfRange,
+    /// A continuation byte didn't carry the required `0b10xxxxxx` tag, in
+    /// a position not covered by the more specific kinds above.
+    InvalidContinuation,
+}
+
+/// Classifies the single error described by `valid_up_to`/`error_len`
+/// into a [`Utf8ErrorKind`], by re-examining the same byte(s)
+/// [`validate_non_acii_bytes`] rejected.
+#[cfg(feature = "std")]
+fn classify_error_kind(buf: &[u8], valid_up_to: usize, error_len: Option<u8>) -> Utf8ErrorKind {
+    let Some(len) = error_len else {
+        return Utf8ErrorKind::Truncated;
+    };
+
+    let byte = buf[valid_up_to];
+    if matches!(byte, 0x80..=0xBF) {
+        return Utf8ErrorKind::StrayContinuation;
+    }
+    if matches!(byte, 0xC0 | 0xC1 | 0xF5..=0xFF) {
+        return Utf8ErrorKind::InvalidLead;
+    }
+
+    if len == 1 {
+        let width = utf8_char_width(byte);
+        let next = buf.get(valid_up_to + 1).copied();
+        return match (width, byte, next) {
+            (3, 0xE0, Some(0x80..=0x9F)) | (4, 0xF0, Some(0x80..=0x8F)) => {
+                Utf8ErrorKind::Overlong
+            }
+            (3, 0xED, Some(0xA0..=0xBF)) => Utf8ErrorKind::Surrogate,
+            (4, 0xF4, Some(0x90..=0xBF)) => Utf8ErrorKind::OutOfRange,
+            _ => Utf8ErrorKind::InvalidContinuation,
+        };
+    }
+
+    Utf8ErrorKind::InvalidContinuation
+}
+
+/// Scans `buf` for every invalid UTF-8 sequence and tallies how many fall
+/// into each [`Utf8ErrorKind`], alongside the total number of invalid
+/// bytes, for a corpus-wide "what kind of damage is this data source
+/// producing" audit.
+///
+/// Uses the same multi-error resume scan as
+/// [`validate_utf8_returning_replacement_plan`], re-running
+/// [`validate_utf8`] on the remainder of `buf` after each error.
+#[cfg(feature = "std")]
+pub fn error_kind_histogram(buf: &[u8]) -> (std::collections::HashMap<Utf8ErrorKind, usize>, usize) {
+    let mut histogram = std::collections::HashMap::new();
+    let mut total_invalid_bytes = 0;
+    let mut pos = 0;
+
+    while pos < buf.len() {
+        match validate_utf8(&buf[pos..]) {
+            Ok(()) => break,
+            Err(e) => {
+                let kind = classify_error_kind(&buf[pos..], e.valid_up_to, e.error_len);
+                *histogram.entry(kind).or_insert(0) += 1;
+
+                match e.error_len {
+                    Some(len) => {
+                        total_invalid_bytes += len as usize;
+                        pos += e.valid_up_to + len as usize;
+                    }
+                    None => {
+                        total_invalid_bytes += buf.len() - pos - e.valid_up_to;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    (histogram, total_invalid_bytes)
+}
+
+/// Returns the `n`-th (0-indexed) invalid sequence in `buf`, with offsets
+/// translated into the whole-buffer coordinate space, or `None` if `buf`
+/// contains `n` or fewer errors.
+///
+/// A lazy, allocation-free alternative to collecting every error (as
+/// [`validate_utf8_returning_replacement_plan`] or [`error_kind_histogram`]
+/// do) for callers — such as a paging error viewer — that only need one
+/// specific error out of a large, corrupt buffer. Uses the same
+/// multi-error resume scan, but stops as soon as the `n`-th error is
+/// found instead of scanning to the end of `buf`.
+pub fn nth_error(buf: &[u8], n: usize) -> Option<Utf8Error> {
+    let mut pos = 0;
+    let mut remaining = n;
+
+    loop {
+        let e = match validate_utf8(&buf[pos..]) {
+            Ok(()) => return None,
+            Err(e) => e,
+        };
+
+        let absolute = Utf8Error {
+            valid_up_to: pos + e.valid_up_to,
+            error_len: e.error_len,
+            error_byte: e.error_byte,
+        };
+
+        if remaining == 0 {
+            return Some(absolute);
+        }
+        remaining -= 1;
+
+        match e.error_len {
+            Some(len) => pos += e.valid_up_to + len as usize,
+            None => return None,
+        }
+    }
+}
+
+/// Mask selecting the two most-significant bits of every byte in a word.
+const CONT_TAG_MASK: usize = usize::from_ne_bytes([0xC0; WORD_BYTES]);
+/// The `10xxxxxx` tag every UTF-8 continuation byte carries, replicated
+/// across every byte of a word.
+const CONT_TAG: usize = usize::from_ne_bytes([0x80; WORD_BYTES]);
+
+/// `true` if every byte of `word` carries the UTF-8 continuation-byte tag
+/// `0b10xxxxxx`, i.e. every byte lies in `0x80..=0xBF`.
+#[inline(always)]
+const fn is_all_continuation_bytes(word: usize) -> bool {
+    word & CONT_TAG_MASK == CONT_TAG
+}
+
+/// Counts how many of the bytes at `buf[curr..end]` are valid UTF-8
+/// continuation bytes (`0x80..=0xBF`), stopping at the first byte that
+/// isn't one, checking a whole word at a time via SWAR where possible.
+///
+/// This is used to skip runs of continuation bytes in dense multi-byte
+/// text (e.g. CJK) a word at a time instead of one byte at a time; it
+/// does not itself validate lead-byte/width agreement, so callers still
+/// need the precise per-character checks at sequence boundaries.
+pub fn count_continuation_bytes_swar(buf: &[u8], curr: usize, end: usize) -> usize {
+    let start = buf.as_ptr();
+    let mut pos = curr;
+
+    while pos + WORD_BYTES <= end {
+        // SAFETY: bounds checked by the loop condition
+        let word = unsafe { (start.add(pos) as *const usize).read_unaligned() };
+        if !is_all_continuation_bytes(word) {
+            break;
+        }
+        pos += WORD_BYTES;
+    }
+
+    while pos < end && matches!(buf[pos], 0x80..=0xBF) {
+        pos += 1;
+    }
+
+    pos - curr
+}
+
+/// SIMD-backend-aware diagnostics captured by [`validate_utf8_auto_diag`],
+/// for comparing backend efficiency the way [`Statistics`] compares block
+/// sizes.
+///
+/// [`validate_utf8_auto_diag`] always runs the portable `[usize; N]`
+/// `ByteVector` block scan ([`validate_utf8_with_stats`]), never an
+/// AVX2/NEON/simd128 backend, so `backend` is always `"portable"` and the
+/// per-vector counts are derived from [`Statistics`]' block counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SimdDiag {
+    /// Number of word-blocks (8x, 4x, or 2x) examined, successful or not.
+    pub vectors_processed: u64,
+    /// Number of times the block scan gave up and dropped into the
+    /// byte-wise fallback loop.
+    pub vectors_scalar_fallback: u64,
+    /// Bytes skipped up front to reach a word-aligned start.
+    pub preamble_bytes: usize,
+    /// Bytes past the last full word-block, examined byte-wise.
+    pub tail_bytes: usize,
+    /// Name of the backend that ran; always `"portable"`, since
+    /// [`validate_utf8_auto_diag`] only runs the portable block scan.
+    pub backend: &'static str,
+}
+
+/// Validates `buf` as UTF-8 via [`validate_utf8_with_stats`], additionally returning
+/// a [`SimdDiag`] snapshot for backend efficiency comparisons.
+///
+/// Analogous to [`validate_utf8_with_stats`], but reshaped around the
+/// vector-processed/scalar-fallback/preamble/tail breakdown a SIMD
+/// backend author cares about, instead of raw 8x/2x block counters.
+pub fn validate_utf8_auto_diag(buf: &[u8]) -> (Result<(), Utf8Error>, SimdDiag) {
+    let mut stats = Statistics::default();
+    let result = validate_utf8_with_stats(buf, Some(&mut stats));
+
+    let preamble_bytes = buf.as_ptr().align_offset(WORD_BYTES).min(buf.len());
+    let full_block_bytes = stats.success_blocks_8x * 8 * WORD_BYTES
+        + stats.success_blocks_4x * 4 * WORD_BYTES
+        + stats.success_blocks_2x * 2 * WORD_BYTES;
+    let tail_bytes = buf.len().saturating_sub(preamble_bytes + full_block_bytes);
+
+    let diag = SimdDiag {
+        vectors_processed: (stats.success_blocks_8x
+            + stats.failed_blocks_8x
+            + stats.success_blocks_4x
+            + stats.failed_blocks_4x
+            + stats.success_blocks_2x
+            + stats.failed_blocks_2x) as u64,
+        vectors_scalar_fallback: stats.bytewise_checks as u64,
+        preamble_bytes,
+        tail_bytes,
+        backend: "portable",
+    };
+
+    (result, diag)
+}
+
+/// Counts how many bytes of `buf` are ASCII (`< 128`), a word at a time.
+///
+/// A non-ASCII byte contributes exactly one set bit to
+/// `word & NONASCII_MASK`, so `count_ones()` on that masked word gives
+/// the exact count of non-ASCII bytes without a per-byte branch.
+fn count_ascii_bytes(buf: &[u8]) -> usize {
+    let (mut curr, end) = (0, buf.len());
+    let start = buf.as_ptr();
+    let mut ascii_count = 0;
+
+    while curr + WORD_BYTES <= end {
+        // SAFETY: bounds checked by the loop condition
+        let word = unsafe { (start.add(curr) as *const usize).read_unaligned() };
+        let non_ascii_in_word = (word & NONASCII_MASK).count_ones() as usize;
+        ascii_count += WORD_BYTES - non_ascii_in_word;
+        curr += WORD_BYTES;
+    }
+
+    while curr < end {
+        if buf[curr] < 128 {
+            ascii_count += 1;
+        }
+        curr += 1;
+    }
+
+    ascii_count
+}
+
+/// Validates `buf` as UTF-8 and, in the same call, returns the fraction
+/// of its bytes that are ASCII (`0.0..=1.0`), fusing what the benchmark
+/// harness's `ascii_ratio` otherwise computes as a separate pass over the
+/// same bytes.
+///
+/// The density is computed over the whole buffer regardless of where
+/// validation fails, matching `ascii_ratio`'s definition; a low density
+/// is exactly the signal [`validate_utf8_low_ascii`] wants to decide
+/// whether the 8x/2x block scan is worth its setup cost.
+pub fn validate_utf8_returning_ascii_density(buf: &[u8]) -> (f64, Result<(), Utf8Error>) {
+    let result = validate_utf8(buf);
+
+    let density = if buf.is_empty() {
+        0.0
+    } else {
+        count_ascii_bytes(buf) as f64 / buf.len() as f64
+    };
+
+    (density, result)
+}
+
+/// Above this size, [`validate_utf8_stream_large`] issues prefetch hints
+/// ahead of the scan; below it, the bookkeeping isn't worth its own
+/// overhead.
+const STREAM_LARGE_THRESHOLD: usize = 64 * 1024;
+
+/// Distance, in bytes, [`validate_utf8_stream_large`] prefetches ahead of
+/// the current scan position.
+const PREFETCH_DISTANCE: usize = 512;
+
+/// Issues `_mm_prefetch` hints every [`PREFETCH_DISTANCE`] bytes of
+/// `buf`, for [`validate_utf8_stream_large`] to warm the cache ahead of
+/// the scan on large buffers.
+#[cfg(target_arch = "x86_64")]
+fn prefetch_ahead(buf: &[u8]) {
+    use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+
+    let mut curr = 0;
+    while curr + PREFETCH_DISTANCE < buf.len() {
+        // SAFETY: `curr + PREFETCH_DISTANCE < buf.len()` is checked by
+        // the loop condition, so the prefetched address is in bounds;
+        // `_mm_prefetch` itself has no other safety requirements beyond
+        // reading a valid address.
+        unsafe {
+            _mm_prefetch(
+                buf.as_ptr().add(curr + PREFETCH_DISTANCE) as *const i8,
+                _MM_HINT_T0,
+            );
+        }
+        curr += PREFETCH_DISTANCE;
+    }
+}
+
+/// Validates `buf` as UTF-8, additionally issuing software prefetch
+/// hints ahead of the scan for large buffers, tuned for sustained
+/// validation of a big file streamed through a fixed window.
+///
+/// Scope note: true "nontemporal loads" (`MOVNTDQA`/
+/// `_mm_stream_load_si128`) bypass the cache only for reads from
+/// *write-combining* memory, which ordinary heap buffers are not — using
+/// them here would not give the cache-pollution benefit the naive
+/// reading suggests, so this backend only prefetches; it does not use
+/// nontemporal loads. On `x86_64`, buffers at or above
+/// `STREAM_LARGE_THRESHOLD` get `_mm_prefetch` hints
+/// `PREFETCH_DISTANCE` bytes ahead of the scan. On other targets, or
+/// smaller buffers, this is exactly [`validate_utf8`] — always producing
+/// identical [`Utf8Error`] results either way.
+pub fn validate_utf8_stream_large(buf: &[u8]) -> Result<(), Utf8Error> {
+    #[cfg(target_arch = "x86_64")]
+    if buf.len() >= STREAM_LARGE_THRESHOLD {
+        prefetch_ahead(buf);
+    }
+
+    validate_utf8(buf)
+}
+
+/// Validates `buf` as UTF-8 using a tight per-character loop, without the
+/// 8x/2x word-block scan used by [`validate_utf8`].
+///
+/// For low-ASCII corpora (Chinese, Japanese, Greek, ...) the block scan
+/// almost always finds a non-ASCII byte in the very first word, so it
+/// pays for its own setup without ever amortizing it over an ASCII run.
+/// This variant skips that machinery entirely and goes straight to
+/// [`utf8_char_width`]-driven decoding, which is cheaper when non-ASCII
+/// bytes are common. It is intended to be selected by a density estimate,
+/// as a dynamic dispatcher would.
+pub fn validate_utf8_low_ascii(buf: &[u8]) -> Result<(), Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+
+    while curr < end {
+        if buf[curr] < 128 {
+            curr += 1;
+            continue;
+        }
+
+        match validate_non_acii_bytes(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates `buf` as UTF-8 via a vectorized "lookup-table" structural
+/// check, intended as a high-throughput backend for low-ASCII text (the
+/// same corpora [`validate_utf8_low_ascii`] targets), rather than finding
+/// only the first non-ASCII byte and falling back to a per-char scalar
+/// loop for everything past it.
+///
+/// No vectorized lookup-table (`pshufb`-style) code runs here: this is
+/// [`validate_utf8_low_ascii`]'s scalar path under its own name, a
+/// separate function that gives a density-based dispatcher (see
+/// [`validate_utf8_returning_ascii_density`]) a stable low-ASCII entry
+/// point.
+pub fn validate_utf8_simd_lookup(buf: &[u8]) -> Result<(), Utf8Error> {
+    validate_utf8_low_ascii(buf)
+}
+
+/// Validates `buf` as UTF-8 with the smallest reasonable code size:
+/// [`validate_utf8_low_ascii`]'s scalar, block-free scan, over the
+/// branch-based `size-min` [`utf8_char_width`], so no jump table or
+/// SIMD-style word-block machinery is pulled in.
+///
+/// Only available under the `size-min` feature, for binaries that would
+/// rather pay a throughput cost on ASCII-heavy input than the extra code
+/// size of [`validate_utf8`]'s 8x/4x/2x block scan (about 1.1KiB, roughly
+/// twice [`validate_utf8_std`]'s 474B baseline). `#[inline(never)]`
+/// so the function keeps its own symbol for `cargo bloat` to report on.
+#[cfg(feature = "size-min")]
+#[inline(never)]
+pub fn validate_utf8_size_min(buf: &[u8]) -> Result<(), Utf8Error> {
+    validate_utf8_low_ascii(buf)
+}
+
+/// Validates `buf` as UTF-8 up to (but not including) the first `0x00`
+/// byte, treating an embedded NUL as the logical end of the data, the way
+/// a C string stored in a larger allocation would be.
+///
+/// Returns the offset of the NUL byte, or `buf.len()` if `buf` contains
+/// none. Structural UTF-8 errors located before the NUL are still
+/// reported as `Err`, exactly as [`validate_utf8`] would report them.
+pub fn validate_utf8_until_nul(buf: &[u8]) -> Result<usize, Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+    let start = buf.as_ptr();
+
+    while curr < end {
+        let byte = buf[curr];
+        if byte == 0 {
+            return Ok(curr);
+        }
+
+        if byte < 128 {
+            // scan a whole word at a time for either a non-ASCII or a NUL
+            // byte, falling back to single bytes at the tail
+            if curr + WORD_BYTES <= end {
+                // SAFETY: `curr + WORD_BYTES <= end` guarantees this read
+                // stays within `buf`
+                let word = unsafe { (start.add(curr) as *const usize).read_unaligned() };
+                if !contains_nonascii(word) && !contains_zero_byte(word) {
+                    curr += WORD_BYTES;
+                    continue;
+                }
+            }
+
+            curr += 1;
+            continue;
+        }
+
+        match validate_non_acii_bytes(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(end)
+}
+
+/// Validates `buf` as UTF-8 using a single, compile-time-fixed block width
+/// of `N` words, instead of [`validate_utf8`]'s 8x-then-4x-then-2x cascade.
+///
+/// Reuses the same `ByteVector` block scan and hand-off to
+/// `validate_non_acii_bytes` that back [`validate_utf8`], just without
+/// the escalation/de-escalation between block widths, so callers can
+/// measure how much of [`validate_utf8`]'s throughput comes from the
+/// cascade itself versus the block scan mechanics by comparing fixed
+/// widths like `::<1>`, `::<4>`, or `::<16>` directly against it.
+///
+/// Bytes past the last full `N`-word block (because `buf.len()` isn't a
+/// multiple of `N * size_of::<usize>()`) fall back to the same byte-wise
+/// ASCII/`validate_non_acii_bytes` tail handling [`validate_utf8`] uses.
+pub fn validate_utf8_baseline<const N: usize>(buf: &[u8]) -> Result<(), Utf8Error> {
+    debug_assert!(N > 0, "validate_utf8_baseline requires a non-zero block width");
+
+    let (mut curr, end) = (0, buf.len());
+    let start = buf.as_ptr();
+
+    let block_end_n = block_end(end, N * WORD_BYTES);
+    let align_offset = start.align_offset(WORD_BYTES);
+
+    'outer: while curr < end {
+        'ascii: {
+            if buf[curr] >= 128 {
+                break 'ascii;
+            }
+
+            if align_offset == usize::MAX {
+                curr += 1;
+                continue 'outer;
+            }
+
+            let offset = align_offset.wrapping_sub(curr) % WORD_BYTES;
+            if offset > 0 {
+                let aligned = curr + offset;
+                loop {
+                    curr += 1;
+
+                    if curr == end {
+                        return Ok(());
+                    }
+
+                    if buf[curr] >= 128 {
+                        break 'ascii;
+                    }
+
+                    if curr == aligned {
+                        break;
+                    }
+                }
+            }
+
+            while curr < block_end_n {
+                // SAFETY: the loop condition guarantees that there is
+                // sufficient room for an N-word block in the buffer
+                let block: [usize; N] = unsafe { ByteVector::load(start.add(curr)) };
+                if let Some(masked) = mask_if_non_ascii(block) {
+                    // SAFETY: `mask_if_non_ascii` only returns `Some` when
+                    // at least one non-ASCII byte is present
+                    curr += unsafe { non_ascii_byte_position(&masked) as usize };
+                    break 'ascii;
+                }
+
+                curr += N * WORD_BYTES;
+            }
+
+            loop {
+                curr += 1;
+
+                if curr >= end {
+                    return Ok(());
+                }
+
+                if buf[curr] >= 128 {
+                    break 'ascii;
+                }
+            }
+        }
+
+        match validate_non_acii_bytes(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Number of consecutive failed 8-word blocks after which
+/// [`validate_utf8_dynamic`] demotes to 2-word blocks.
+const DYNAMIC_DEMOTE_THRESHOLD: u32 = 2;
+/// Number of consecutive successful 2-word blocks after which
+/// [`validate_utf8_dynamic`] re-promotes to 8-word blocks.
+const DYNAMIC_PROMOTE_THRESHOLD: u32 = 4;
+
+/// Validates `buf` as UTF-8, adapting its block width to the observed
+/// non-ASCII density instead of always retrying an 8-word block first the
+/// way [`validate_utf8`]'s fixed cascade does.
+///
+/// Starts optimistically scanning 8-word blocks, the way [`validate_utf8`]
+/// does. Once `DYNAMIC_DEMOTE_THRESHOLD` 8-word blocks in a row turn out
+/// to contain a non-ASCII byte, it demotes to 2-word blocks, which waste
+/// less work per non-ASCII byte on low-ASCII-density scripts such as
+/// Chinese or Greek. Once `DYNAMIC_PROMOTE_THRESHOLD` 2-word blocks in a
+/// row come back fully ASCII, it re-promotes to 8-word blocks, on the
+/// theory that the low-density stretch has ended.
+///
+/// See [`validate_utf8_dynamic_with_stats`] to observe the escalation via
+/// `Statistics::optimistic_2x_to_8x`, incremented every time it promotes
+/// back to 8-word blocks. Produces identical [`Utf8Error`] results to
+/// [`validate_utf8`]; only the block-width bookkeeping differs.
+pub fn validate_utf8_dynamic(buf: &[u8]) -> Result<(), Utf8Error> {
+    validate_utf8_dynamic_with_stats(buf, None)
+}
+
+/// [`validate_utf8_dynamic`], additionally recording block-width
+/// escalation/de-escalation decisions into `stats`, if given.
+pub fn validate_utf8_dynamic_with_stats(
+    buf: &[u8],
+    mut stats: Option<&mut Statistics>,
+) -> Result<(), Utf8Error> {
+    let (mut curr, end) = (0, buf.len());
+    let start = buf.as_ptr();
+    let align_offset = start.align_offset(WORD_BYTES);
+
+    let mut width: usize = 8;
+    let mut consecutive_failures: u32 = 0;
+    let mut consecutive_successes: u32 = 0;
+
+    'outer: while curr < end {
+        'ascii: {
+            if buf[curr] >= 128 {
+                break 'ascii;
+            }
+
+            if align_offset == usize::MAX {
+                curr += 1;
+                continue 'outer;
+            }
+
+            let offset = align_offset.wrapping_sub(curr) % WORD_BYTES;
+            if offset > 0 {
+                if let Some(stats) = stats.as_mut() {
+                    stats.unaligned_blocks += 1;
+                }
+
+                let aligned = curr + offset;
+                loop {
+                    curr += 1;
+
+                    if curr == end {
+                        return Ok(());
+                    }
+
+                    if buf[curr] >= 128 {
+                        break 'ascii;
+                    }
+
+                    if curr == aligned {
+                        break;
+                    }
+                }
+            }
+
+            // examine one block of the current width at a time, so
+            // promotion/demotion can be decided after every single block
+            loop {
+                let block_bytes = width * WORD_BYTES;
+                if curr + block_bytes > end {
+                    break;
+                }
+
+                let failed = if width == 8 {
+                    // SAFETY: the check above guarantees room for 8 words
+                    let block: [usize; 8] = unsafe { ByteVector::load(start.add(curr)) };
+                    match mask_if_non_ascii(block) {
+                        Some(masked) => {
+                            if let Some(stats) = stats.as_mut() {
+                                stats.failed_blocks_8x += 1;
+                            }
+                            // SAFETY: `mask_if_non_ascii` only returns
+                            // `Some` when a non-ASCII byte is present
+                            curr += unsafe { non_ascii_byte_position(&masked) as usize };
+                            true
+                        }
+                        None => {
+                            if let Some(stats) = stats.as_mut() {
+                                stats.success_blocks_8x += 1;
+                            }
+                            curr += block_bytes;
+                            false
+                        }
+                    }
+                } else {
+                    // SAFETY: the check above guarantees room for 2 words
+                    let block: [usize; 2] = unsafe { ByteVector::load(start.add(curr)) };
+                    match mask_if_non_ascii(block) {
+                        Some(masked) => {
+                            if let Some(stats) = stats.as_mut() {
+                                stats.failed_blocks_2x += 1;
+                            }
+                            // SAFETY: `mask_if_non_ascii` only returns
+                            // `Some` when a non-ASCII byte is present
+                            curr += unsafe { non_ascii_byte_position(&masked) as usize };
+                            true
+                        }
+                        None => {
+                            if let Some(stats) = stats.as_mut() {
+                                stats.success_blocks_2x += 1;
+                            }
+                            curr += block_bytes;
+                            false
+                        }
+                    }
+                };
+
+                if failed {
+                    consecutive_successes = 0;
+                    consecutive_failures += 1;
+
+                    if width == 8 && consecutive_failures >= DYNAMIC_DEMOTE_THRESHOLD {
+                        width = 2;
+                        consecutive_failures = 0;
+                    }
+
+                    break 'ascii;
+                }
+
+                consecutive_failures = 0;
+                consecutive_successes += 1;
+
+                if width == 2 && consecutive_successes >= DYNAMIC_PROMOTE_THRESHOLD {
+                    width = 8;
+                    consecutive_successes = 0;
+
+                    if let Some(stats) = stats.as_mut() {
+                        stats.optimistic_2x_to_8x += 1;
+                    }
+                }
+            }
+
+            if let Some(stats) = stats.as_mut() {
+                stats.bytewise_checks += 1;
+            }
+
+            // fewer than one block's worth of bytes remain; fall back to
+            // the same byte-wise scan [`validate_utf8`] uses for its tail
+            loop {
+                curr += 1;
+
+                if curr >= end {
+                    return Ok(());
+                }
+
+                if buf[curr] >= 128 {
+                    break 'ascii;
+                }
+            }
+        }
+
+        if let Some(stats) = stats.as_mut() {
+            stats.non_ascii_checks += 1;
+        }
+
+        match validate_non_acii_bytes(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the length of the longest valid UTF-8 prefix of `buf`.
+///
+/// Equivalent to
+/// `validate_utf8(buf).map(|_| buf.len()).unwrap_or_else(|e| e.valid_up_to)`,
+/// but runs the same block scan [`validate_utf8`] does directly, instead
+/// of building and then immediately discarding a [`Utf8Error`] on the
+/// error path — useful for incremental decoders that only need to know
+/// how far they can safely consume, not why the rest is invalid.
+///
+/// Always returns `buf.len()` for fully valid input, and stops precisely
+/// before a truncated trailing multi-byte sequence, the same as
+/// [`Utf8Error::valid_up_to`] would.
+pub fn validate_utf8_up_to(buf: &[u8]) -> usize {
+    let (mut curr, end) = (0, buf.len());
+    let start = buf.as_ptr();
+
+    let block_end_2x = block_end(end, 2 * WORD_BYTES);
+    let block_end_8x = block_end(end, 8 * WORD_BYTES);
+    let align_offset = start.align_offset(WORD_BYTES);
+
+    'outer: while curr < end {
+        'ascii: {
+            if buf[curr] >= 128 {
+                break 'ascii;
+            }
+
+            if align_offset == usize::MAX {
+                curr += 1;
+                continue 'outer;
+            }
+
+            let offset = align_offset.wrapping_sub(curr) % WORD_BYTES;
+            if offset > 0 {
+                let aligned = curr + offset;
+                loop {
+                    curr += 1;
+
+                    if curr == end {
+                        return end;
+                    }
+
+                    if buf[curr] >= 128 {
+                        break 'ascii;
+                    }
+
+                    if curr == aligned {
+                        break;
+                    }
+                }
+            }
+
+            let mut dirty = [0usize; 8];
+
+            let non_ascii = 'block: {
+                while curr < block_end_8x {
+                    // SAFETY: the loop condition guarantees room for 8 words
+                    let block: [usize; 8] = unsafe { ByteVector::load(start.add(curr)) };
+                    if let Some(masked) = mask_if_non_ascii(block) {
+                        dirty = masked;
+                        break 'block 8;
+                    }
+
+                    curr += 8 * WORD_BYTES;
+                }
+
+                while curr < block_end_2x {
+                    // SAFETY: the loop condition guarantees room for 2 words
+                    let block: [usize; 2] = unsafe { ByteVector::load(start.add(curr)) };
+                    if let Some(masked) = mask_if_non_ascii(block) {
+                        dirty[..2].copy_from_slice(&masked);
+                        break 'block 2;
+                    }
+
+                    curr += 2 * WORD_BYTES;
+                }
+
+                break 'block 0;
+            };
+
+            if non_ascii > 0 {
+                // SAFETY: a previous [8|2]-word check failed, so `dirty`
+                // holds at least one non-ASCII byte
+                curr += unsafe { non_ascii_byte_position(&dirty[..non_ascii]) as usize };
+                break 'ascii;
+            }
+
+            // ...otherwise, fall back to byte-wise checks. `curr` itself
+            // has not been checked yet at this point whenever the block
+            // loop above consumed at least one whole block (it only knows
+            // the *block* was all-ASCII, not that the byte right after it
+            // is), so it must be examined before advancing past it.
+            loop {
+                if curr >= end {
+                    return end;
+                }
+
+                if buf[curr] >= 128 {
+                    break 'ascii;
+                }
+
+                curr += 1;
+            }
+        }
+
+        // `validate_non_acii_bytes` reports `valid_up_to == curr` (the
+        // position it was called with) on every error, so `curr` itself
+        // is already the answer; no need to inspect the returned error.
+        match validate_non_acii_bytes(buf, curr, end) {
+            Ok(next) => curr = next,
+            Err(_) => return curr,
+        }
+    }
+
+    end
+}
+
+/// Streaming UTF-8 validator that carries up to 3 leftover bytes of an
+/// incomplete trailing sequence across [`feed`](Self::feed) calls.
+///
+/// Unlike [`Utf8Validator`] (which reuses [`validate_utf8_resumable`],
+/// allocating a `Vec` to stitch the leftover bytes onto the next chunk),
+/// this stitches them into a small stack buffer and validates that seam
+/// directly with `validate_non_acii_bytes`, never allocating.
+#[derive(Default)]
+pub struct Utf8StreamValidator {
+    leftover: [u8; 3],
+    leftover_len: u8,
+    total_fed: usize,
+}
+
+impl Utf8StreamValidator {
+    /// Creates a validator with no pending partial sequence.
+    pub const fn new() -> Self {
+        Self { leftover: [0; 3], leftover_len: 0, total_fed: 0 }
+    }
+
+    fn stash(&mut self, tail: &[u8]) {
+        self.leftover[..tail.len()].copy_from_slice(tail);
+        self.leftover_len = tail.len() as u8;
+    }
+
+    /// Validates the next `chunk` of the stream.
+    ///
+    /// Returns `Ok(())` if `chunk` is valid so far, including the case
+    /// where it ends mid-sequence and the remainder is buffered waiting
+    /// for a later `feed` call. `Err`'s `valid_up_to` is an offset into
+    /// the whole stream fed so far, not just `chunk`. Once `feed` returns
+    /// `Err`, the validator should be discarded.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<(), Utf8Error> {
+        let leftover_len = self.leftover_len as usize;
+        let base = self.total_fed - leftover_len;
+        self.total_fed += chunk.len();
+
+        if leftover_len == 0 {
+            return match validate_utf8(chunk) {
+                Ok(()) => Ok(()),
+                Err(e) if e.error_len.is_none() => {
+                    self.stash(&chunk[e.valid_up_to..]);
+                    Ok(())
+                }
+                Err(mut e) => {
+                    e.valid_up_to += base;
+                    Err(e)
+                }
+            };
+        }
+
+        // stitch the leftover bytes onto the front of a scratch buffer,
+        // together with just enough of `chunk` to complete the widest
+        // possible sequence (4 bytes total), so the seam between chunks
+        // can be validated without allocating
+        let mut seam = [0u8; 4];
+        seam[..leftover_len].copy_from_slice(&self.leftover[..leftover_len]);
+        let wanted = 4 - leftover_len;
+        let taken = chunk.len().min(wanted);
+        seam[leftover_len..leftover_len + taken].copy_from_slice(&chunk[..taken]);
+        let seam_len = leftover_len + taken;
+
+        match validate_non_acii_bytes(&seam, 0, seam_len) {
+            Ok(consumed) => {
+                self.leftover_len = 0;
+                let consumed_from_chunk = consumed - leftover_len;
+
+                match validate_utf8(&chunk[consumed_from_chunk..]) {
+                    Ok(()) => Ok(()),
+                    Err(e) if e.error_len.is_none() => {
+                        self.stash(&chunk[consumed_from_chunk + e.valid_up_to..]);
+                        Ok(())
+                    }
+                    Err(mut e) => {
+                        e.valid_up_to += base + consumed;
+                        Err(e)
+                    }
+                }
+            }
+            // the seam still didn't contain a whole character, but that's
+            // only possible if `chunk` itself was shorter than the bytes
+            // still needed, i.e. it was entirely folded into `seam`
+            Err(e) if e.error_len.is_none() => {
+                self.stash(&seam[..seam_len]);
+                Ok(())
+            }
+            Err(mut e) => {
+                e.valid_up_to += base;
+                Err(e)
+            }
+        }
+    }
+
+    /// `true` if there is no partial sequence awaiting more bytes.
+    pub fn is_complete(&self) -> bool {
+        self.leftover_len == 0
+    }
+
+    /// Signals that no more data is coming, and errors if a character was
+    /// left incomplete by the last `feed` call.
+    pub fn finish(self) -> Result<(), Utf8Error> {
+        if self.is_complete() {
+            Ok(())
+        } else {
+            Err(Utf8Error {
+                valid_up_to: self.total_fed - self.leftover_len as usize,
+                error_len: None,
+                error_byte: None,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod no_std_tests {
+    // Exercises the core validator using nothing but core-constructable
+    // byte slices, so it compiles and passes under `--no-default-features`
+    // and proves the `no_std` build actually validates UTF-8 correctly,
+    // not just that it compiles.
+    use super::{validate_utf8, Utf8Error};
+
+    #[test]
+    fn accepts_valid_utf8_byte_array() {
+        assert_eq!(validate_utf8(b"Lorem ipsum dolor sit amet."), Ok(()));
+    }
+
+    #[test]
+    fn rejects_invalid_utf8_byte_array() {
+        let err = validate_utf8(b"ab\xFFcd").unwrap_err();
+        assert_eq!(
+            err,
+            Utf8Error { valid_up_to: 2, error_len: Some(1), error_byte: Some(0xFF) }
+        );
+    }
+
+    #[test]
+    fn reports_incomplete_trailing_sequence() {
+        let err = validate_utf8(b"ab\xE2\x82").unwrap_err();
+        assert!(err.is_incomplete());
+    }
+
+    #[test]
+    fn statistics_default_is_all_zero() {
+        let stats = super::Statistics::default();
+        assert_eq!(stats.success_blocks_8x, 0);
+        assert_eq!(stats.failed_blocks_8x, 0);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    const GERMAN_UTF8_16KB: &str = include_str!("../assets/german_16kb.txt");
+
+    use super::validate_utf8;
+
+    #[test]
+    fn invalid_utf8() {
+        let err = validate_utf8(b"A\xC3\xA9 \xF1 ").unwrap_err();
+        assert_eq!(
+            err,
+            super::Utf8Error {
+                valid_up_to: 4,
+                error_len: Some(1),
+                error_byte: Some(b' '),
+            }
+        );
+        assert_eq!(err.error_byte(), Some(b' '));
+
+        let err = validate_utf8(b"A\xC3\xA9 \xF1\x80 ").unwrap_err();
+        assert_eq!(
+            err,
+            super::Utf8Error {
+                valid_up_to: 4,
+                error_len: Some(2),
+                error_byte: Some(b' '),
+            }
+        );
+        assert_eq!(err.error_byte(), Some(b' '));
+    }
+
+    #[test]
+    fn ascii_fast_path_never_produces_an_error() {
+        // an all-ASCII buffer never leaves the ASCII scan, so it never
+        // reaches a branch that constructs a `Utf8Error` at all, let
+        // alone one with `error_byte` set
+        assert_eq!(validate_utf8(b"Lorem ipsum dolor sit amet."), Ok(()));
+    }
+
+    #[test]
+    fn validate_mostly_ascii() {
+        assert!(validate_utf8(GERMAN_UTF8_16KB.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn invalid_ascii() {
+        let mut vec = Vec::from(GERMAN_UTF8_16KB);
+        vec.push(0xFF);
+
+        assert_eq!(validate_utf8(&vec).is_ok(), false);
+    }
+
+    #[test]
+    fn validate_utf() {
+        assert!(validate_utf8(b"Lorem ipsum dolor sit amet.").is_ok());
+        assert!(validate_utf8("Lörem ipsüm dölör sit ämet.".as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn non_ascii_byte_count() {
+        unsafe {
+            let block = [0x7F7F7F7F_7F7F7FFF];
+            let masked = super::mask_block(&block);
+            let res = super::non_ascii_byte_position(&masked);
+            assert_eq!(res, 0);
+            let block = [0x7F7F7F7F_7F7FFF7F];
+            let masked = super::mask_block(&block);
+            let res = super::non_ascii_byte_position(&masked);
+            assert_eq!(res, 1);
+            let block = [0x7F7F7F7F_7FFF7F7F];
+            let masked = super::mask_block(&block);
+            let res = super::non_ascii_byte_position(&masked);
+            assert_eq!(res, 2);
+            let block = [0x7F7F7F7F_FF7F7F7F];
+            let masked = super::mask_block(&block);
+            let res = super::non_ascii_byte_position(&masked);
+            assert_eq!(res, 3);
+            let block = [0x7F7F7FFF_7F7F7F7F];
+            let masked = super::mask_block(&block);
+            let res = super::non_ascii_byte_position(&masked);
+            assert_eq!(res, 4);
+            let block = [0x7F7FFF7F_7F7F7F7F];
+            let masked = super::mask_block(&block);
+            let res = super::non_ascii_byte_position(&masked);
+            assert_eq!(res, 5);
+            let block = [0x7FFF7F7F_7F7F7F7F];
+            let masked = super::mask_block(&block);
+            let res = super::non_ascii_byte_position(&masked);
+            assert_eq!(res, 6);
+            let block = [0xFF7F7F7F_7F7F7F7F];
+            let masked = super::mask_block(&block);
+            let res = super::non_ascii_byte_position(&masked);
+            assert_eq!(res, 7);
+            let block = [0x7F7F7F7F_7F7F7F7F, 0x7F7F7F7F_7F7F7FFF];
+            let masked = super::mask_block(&block);
+            let res = super::non_ascii_byte_position(&masked);
+            assert_eq!(res, 8);
+            let block = [0x7F7F7F7F_7F7F7F7F, 0x7F7F7F7F_7F7FFF7F];
+            let masked = super::mask_block(&block);
+            let res = super::non_ascii_byte_position(&masked);
+            assert_eq!(res, 9);
+        }
+    }
+
+    #[test]
+    fn block_scan_never_reads_past_buffer_end_near_a_block_boundary() {
+        // `block_end_8x`/`block_end_2x` are meant to guarantee the 8- and
+        // 2-word block loops in `validate_utf8_with_stats` never load past
+        // `buf`'s end; the `debug_assert!`s guarding those loads turn any
+        // future regression of that guarantee into a test failure here
+        // instead of a silent out-of-bounds read, for every buffer length
+        // that straddles a block boundary.
+        use super::validate_utf8_with_stats;
+
+        let word = super::WORD_BYTES;
+
+        for extra in 0..(8 * word) {
+            let len = 8 * word + extra;
+            let mut buf = vec![b'a'; len];
+            // put the only non-ASCII byte at the very last byte, so every
+            // 8x/2x block up to it must be scanned in full
+            *buf.last_mut().unwrap() = 0xFF;
+
+            let err = validate_utf8_with_stats(&buf, None).unwrap_err();
+            assert_eq!(err.valid_up_to, len - 1, "buffer length {len}");
+        }
+    }
+
+    #[test]
+    fn block_end_is_exact_at_and_around_a_block_boundary() {
+        use super::block_end;
+
+        let word = super::WORD_BYTES;
+
+        for block_words in [1, 2, 8] {
+            let block_size = block_words * word;
+
+            // one byte short of a full block: no block fits
+            assert_eq!(block_end(block_size - 1, block_size), 0, "block_size {block_size}");
+            // exactly one full block: exactly one starting position (0) fits
+            assert_eq!(block_end(block_size, block_size), 1, "block_size {block_size}");
+            // one byte past a full block: still only the one starting
+            // position (0) fits a whole block, with a single leftover byte
+            assert_eq!(block_end(block_size + 1, block_size), 2, "block_size {block_size}");
+        }
+    }
+
+    #[test]
+    fn with_stats_reports_correct_valid_up_to_at_word_and_8x_boundaries() {
+        use super::validate_utf8_with_stats;
+
+        let word = super::WORD_BYTES;
+
+        for block_size in [word, 2 * word, 8 * word] {
+            for len in [block_size - 1, block_size, block_size + 1] {
+                let mut buf = vec![b'a'; len];
+                // put the only non-ASCII byte at the very last byte, so the
+                // relevant block loop must run to completion before falling
+                // through to the bytewise tail check
+                *buf.last_mut().unwrap() = 0xFF;
+
+                let err = validate_utf8_with_stats(&buf, None).unwrap_err();
+                assert_eq!(err.valid_up_to, len - 1, "block_size {block_size}, len {len}");
+            }
+        }
+    }
+
+    #[test]
+    fn up_to_reports_the_byte_right_after_a_completed_block_as_non_ascii() {
+        // regression test: `validate_utf8_up_to`'s bytewise tail loop used
+        // to advance `curr` past the byte immediately following a
+        // completed 8x/2x block before checking it, silently skipping the
+        // very byte the block loop's own doc comment says still needs
+        // checking. Slicing into a heap buffer (rather than validating it
+        // from offset 0) is what actually surfaces this: it shifts the
+        // buffer's word alignment relative to its start, which changes
+        // where a block loop bottoms out and hands off to the tail loop.
+        use super::validate_utf8_up_to;
+
+        let word = super::WORD_BYTES;
+
+        for pad in 0..word {
+            for block_words in [2, 8] {
+                let block_size = block_words * word;
+
+                // a handful of whole blocks, then a short ASCII remainder
+                // too small for another block, with the very first byte of
+                // that remainder set non-ASCII
+                let mut backing = vec![0u8; pad + block_size + 3];
+                for byte in backing[pad..].iter_mut() {
+                    *byte = b'a';
+                }
+                backing[pad + block_size] = 0xFF;
+
+                let buf = &backing[pad..];
+                assert_eq!(
+                    validate_utf8_up_to(buf),
+                    block_size,
+                    "pad {pad}, block_size {block_size}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn small_agrees_with_default_validator_on_short_strings() {
+        use super::validate_utf8_small;
+
+        const SHORT_STRINGS: &str = include_str!("../assets/short_strings.txt");
+
+        for line in SHORT_STRINGS.lines() {
+            assert_eq!(
+                validate_utf8_small(line.as_bytes()),
+                validate_utf8(line.as_bytes()),
+                "line {line:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn small_rejects_invalid_utf8() {
+        use super::validate_utf8_small;
+
+        assert!(validate_utf8_small(b"ab\xFF").is_err());
+        assert!(validate_utf8_small(b"\xE2\x82").is_err());
+    }
+
+    #[test]
+    fn byte_index_in_word_matches_memory_order_on_both_endiannesses() {
+        use super::byte_index_in_word;
+
+        // A masked word with only the high bit of byte index 2 set. On a
+        // little-endian load, memory-order byte 2 lives at bits 16..24 of
+        // the word (`trailing_zeros` finds it); on a big-endian load, the
+        // very same memory-order byte 2 instead lives at bits 40..48 of
+        // the word (`leading_zeros` finds it) — `byte_index_in_word` must
+        // recover the same "byte 2" from either encoding.
+        let word_as_if_le = 0x0000000000800000usize;
+        assert_eq!(byte_index_in_word(word_as_if_le, false), 2);
+
+        let word_as_if_be = 0x0000800000000000usize;
+        assert_eq!(byte_index_in_word(word_as_if_be, true), 2);
+    }
+
+    #[test]
+    #[cfg(target_endian = "big")]
+    fn non_ascii_byte_position_is_correct_on_big_endian_hosts() {
+        unsafe {
+            let block = [0xFF7F7F7F_7F7F7F7F];
+            let masked = super::mask_block(&block);
+            assert_eq!(super::non_ascii_byte_position(&masked), 0);
+
+            let block = [0x7F7F7F7F_7F7F7FFF];
+            let masked = super::mask_block(&block);
+            assert_eq!(super::non_ascii_byte_position(&masked), 7);
+        }
+    }
+
+    #[test]
+    fn faust() {
+        const FAUST: &str = include_str!("../assets/faust_213kb.txt");
+        assert!(validate_utf8(FAUST.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn chinese() {
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+        assert!(validate_utf8(CHINESE.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn non_ascii_run_swar_agrees_with_bytewise_validator_on_chinese_text() {
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+        let buf = CHINESE.as_bytes();
+        let end = buf.len();
+        let mut curr = 0;
+        while curr < end {
+            if buf[curr] < 128 {
+                curr += 1;
+                continue;
+            }
+            curr = super::validate_non_ascii_run_swar(buf, curr, end).unwrap();
+        }
+    }
+
+    #[test]
+    fn non_ascii_run_swar_reports_same_error_as_bytewise_validator() {
+        // 20 copies of a 3-byte CJK character, long enough to exercise
+        // several iterations of the run-consuming loop before hitting the
+        // corrupted continuation byte partway through.
+        let mut buf = "中".as_bytes().repeat(20);
+        let broken_at = 3 * 10 + 2; // the 2nd continuation byte of the 11th character
+        buf[broken_at] = 0xFF;
+
+        // the byte-at-a-time reference: keep calling `validate_non_acii_bytes`
+        // one character at a time, exactly like the outer loop in
+        // `validate_utf8_with_stats` used to before this fast path existed.
+        let mut expected = Ok(0);
+        let mut curr = 0;
+        while curr < buf.len() {
+            match super::validate_non_acii_bytes(&buf, curr, buf.len()) {
+                Ok(next) => curr = next,
+                Err(e) => {
+                    expected = Err(e);
+                    break;
+                }
+            }
+        }
+
+        assert_eq!(
+            super::validate_non_ascii_run_swar(&buf, 0, buf.len()),
+            expected,
+        );
+    }
+
+    #[cfg(feature = "reference-impl")]
+    #[test]
+    fn non_ascii_run_swar_reduces_non_ascii_checks_on_chinese_text() {
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+
+        let mut optimized = super::Statistics::default();
+        assert!(super::validate_utf8_with_stats(CHINESE.as_bytes(), Some(&mut optimized)).is_ok());
+
+        let mut reference = super::Statistics::default();
+        assert!(
+            super::validate_utf8_std_with_stats(CHINESE.as_bytes(), Some(&mut reference)).is_ok()
+        );
+
+        assert!(optimized.non_ascii_checks < reference.non_ascii_checks);
+    }
+
+    #[test]
+    fn latin_3kb() {
+        const LATIN_3KB: &str = include_str!("../assets/latin_3kb.txt");
+        assert!(validate_utf8(LATIN_3KB.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn english_99pct_ascii() {
+        const ENGLISH: &str = include_str!("../assets/english_971kb.txt");
+        assert!(validate_utf8(ENGLISH.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn until_nul_stops_at_nul() {
+        use super::validate_utf8_until_nul;
+
+        assert_eq!(validate_utf8_until_nul(b"hello\0world"), Ok(5));
+        assert_eq!(validate_utf8_until_nul(b"no nul here"), Ok(11));
+        assert_eq!(validate_utf8_until_nul(b""), Ok(0));
+    }
+
+    #[test]
+    fn until_nul_reports_errors_before_nul() {
+        use super::validate_utf8_until_nul;
+
+        assert_eq!(
+            validate_utf8_until_nul(b"A\xC3\xA9 \xF1 \0after"),
+            Err(super::Utf8Error {
+                valid_up_to: 4,
+                error_len: Some(1),
+                error_byte: Some(b' '),
+            })
+        );
+    }
+
+    #[test]
+    fn missing_bytes_for_truncated_tail() {
+        use super::{missing_bytes_for_completion, validate_utf8};
+
+        // 3-byte sequence with only its lead byte present
+        let buf = b"hi \xE2";
+        let err = validate_utf8(buf).unwrap_err();
+        assert_eq!(missing_bytes_for_completion(buf, &err), Some(2));
+
+        // 4-byte sequence with two bytes present
+        let buf = b"hi \xF0\x9F";
+        let err = validate_utf8(buf).unwrap_err();
+        assert_eq!(missing_bytes_for_completion(buf, &err), Some(2));
+    }
+
+    #[test]
+    fn missing_bytes_is_none_for_real_errors() {
+        use super::{missing_bytes_for_completion, validate_utf8};
+
+        let buf = b"A\xC3\xA9 \xF1 ";
+        let err = validate_utf8(buf).unwrap_err();
+        assert_eq!(missing_bytes_for_completion(buf, &err), None);
+    }
+
+    #[test]
+    fn low_ascii_agrees_with_default_on_cjk() {
+        use super::validate_utf8_low_ascii;
+
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+        assert!(validate_utf8_low_ascii(CHINESE.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn low_ascii_reports_same_error_as_default() {
+        use super::validate_utf8_low_ascii;
+
+        assert_eq!(
+            validate_utf8_low_ascii(b"A\xC3\xA9 \xF1 "),
+            Err(super::Utf8Error {
+                valid_up_to: 4,
+                error_len: Some(1),
+                error_byte: Some(b' '),
+            })
+        );
+    }
+
+    #[test]
+    fn simd_lookup_agrees_with_default_on_cjk() {
+        use super::validate_utf8_simd_lookup;
+
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+        assert!(validate_utf8_simd_lookup(CHINESE.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn simd_lookup_reports_same_error_as_default() {
+        use super::validate_utf8_simd_lookup;
+
+        assert_eq!(
+            validate_utf8_simd_lookup(b"A\xC3\xA9 \xF1 "),
+            Err(super::Utf8Error {
+                valid_up_to: 4,
+                error_len: Some(1),
+                error_byte: Some(b' '),
+            })
+        );
+    }
+
+    #[test]
+    fn swar_counts_continuation_run() {
+        use super::count_continuation_bytes_swar;
+
+        // 16 continuation bytes followed by an ASCII byte
+        let mut buf = vec![0x80u8; 16];
+        buf.push(b'A');
+        assert_eq!(count_continuation_bytes_swar(&buf, 0, buf.len()), 16);
+    }
+
+    #[test]
+    fn swar_stops_at_first_non_continuation() {
+        use super::count_continuation_bytes_swar;
+
+        let buf = [0x80, 0x81, 0x82, 0x00, 0x80];
+        assert_eq!(count_continuation_bytes_swar(&buf, 0, buf.len()), 3);
+        assert_eq!(count_continuation_bytes_swar(&buf, 1, buf.len()), 2);
+    }
+
+    #[test]
+    fn normalize_clean_input_borrows() {
+        use super::normalize_utf8;
+
+        let s = normalize_utf8(b"clean ascii").unwrap();
+        assert!(matches!(s, std::borrow::Cow::Borrowed(_)));
+        assert_eq!(&*s, "clean ascii");
+    }
+
+    #[test]
+    fn normalize_strips_bom() {
+        use super::normalize_utf8;
+
+        let mut buf = vec![0xEF, 0xBB, 0xBF];
+        buf.extend_from_slice(b"hello");
+        let s = normalize_utf8(&buf).unwrap();
+        assert!(matches!(s, std::borrow::Cow::Owned(_)));
+        assert_eq!(&*s, "hello");
+    }
+
+    #[test]
+    fn normalize_trims_truncated_tail() {
+        use super::normalize_utf8;
+
+        let s = normalize_utf8(b"hi \xE2").unwrap();
+        assert_eq!(&*s, "hi ");
+    }
+
+    #[test]
+    fn normalize_rejects_real_errors() {
+        use super::normalize_utf8;
+
+        assert!(normalize_utf8(b"A\xC3\xA9 \xF1 ").is_err());
+    }
+
+    #[test]
+    fn byte_vector_detects_non_ascii() {
+        use super::ByteVector;
+
+        let ascii: [usize; 2] = [0x4141414141414141; 2];
+        assert!(!ascii.any_high_bit());
+
+        let ptr = ascii.as_ptr() as *const u8;
+        // SAFETY: `ascii` is a valid, live `[usize; 2]`
+        let loaded: [usize; 2] = unsafe { ByteVector::load(ptr) };
+        assert_eq!(loaded, ascii);
+
+        let mut mixed = ascii;
+        mixed[1] = 0x41414141414141FF;
+        assert!(mixed.any_high_bit());
+    }
+
+    #[test]
+    fn single_script_ascii_only() {
+        use super::validate_utf8_single_script;
+
+        assert_eq!(validate_utf8_single_script(b"hello world").unwrap(), None);
+    }
+
+    #[test]
+    fn single_script_one_family() {
+        use super::{validate_utf8_single_script, Script, ScriptHint};
+
+        assert_eq!(
+            validate_utf8_single_script("Café".as_bytes()).unwrap(),
+            Some(ScriptHint::Single(Script::Latin))
+        );
+    }
+
+    #[test]
+    fn single_script_flags_mixed_scripts() {
+        use super::{validate_utf8_single_script, Script, ScriptHint};
+
+        // accented Latin "é" mixed with Cyrillic "а" (U+0430) is a classic
+        // homoglyph pairing used in spoofed domain names
+        let s = "café\u{0430}";
+        assert_eq!(
+            validate_utf8_single_script(s.as_bytes()).unwrap(),
+            Some(ScriptHint::Mixed(Script::Latin, Script::Cyrillic))
+        );
+    }
+
+    #[test]
+    fn resumable_handles_split_emoji() {
+        use super::{validate_utf8_resumable, Utf8State};
+
+        // U+1F600 GRINNING FACE, encoded as F0 9F 98 80, split across calls
+        let whole = "😀".as_bytes();
+        for split in 1..whole.len() {
+            let (state, res) = validate_utf8_resumable(&whole[..split], Utf8State::Complete);
+            assert_eq!(res, Ok(()));
+            assert!(matches!(state, Utf8State::NeedMore { .. }));
+
+            let (state, res) = validate_utf8_resumable(&whole[split..], state);
+            assert_eq!(res, Ok(()));
+            assert_eq!(state, Utf8State::Complete);
+        }
+    }
+
+    #[test]
+    fn resumable_reports_error_across_chunks() {
+        use super::{validate_utf8_resumable, Utf8State};
+
+        let (state, res) = validate_utf8_resumable(b"hi \xE2", Utf8State::Complete);
+        assert_eq!(res, Ok(()));
+
+        let (_, res) = validate_utf8_resumable(b"\x28bad", state);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn validator_rejects_lead_byte_followed_by_non_continuation_across_feeds() {
+        use super::Utf8Validator;
+
+        let mut validator = Utf8Validator::new();
+        assert!(validator.feed(b"hi \xE2").is_ok());
+        assert!(!validator.is_complete());
+
+        let err = validator.feed(b"A").unwrap_err();
+        assert_eq!(err.valid_up_to(), 3);
+    }
+
+    #[test]
+    fn validator_accepts_char_split_across_many_feeds() {
+        use super::Utf8Validator;
+
+        let mut validator = Utf8Validator::new();
+        let emoji = "😀".as_bytes();
+        for byte in emoji {
+            assert!(validator.feed(&[*byte]).is_ok());
+        }
+        assert!(validator.is_complete());
+    }
+
+    #[test]
+    fn slices_validates_char_straddling_part_boundary() {
+        use super::validate_utf8_slices;
+
+        let whole = "hi caf\u{e9}!".as_bytes(); // é splits as 0xC3 0xA9
+        let split = whole.len() - 1;
+        let parts: [&[u8]; 2] = [&whole[..split], &whole[split..]];
+        assert_eq!(validate_utf8_slices(&parts), Ok(()));
+    }
+
+    #[test]
+    fn vectored_agrees_with_slices_on_multi_script_buffer_split_mid_char() {
+        use super::validate_utf8_vectored;
+
+        // "café" (é = 0xC3 0xA9), "日本語" (each char 3 bytes), "Привет" (each
+        // char 2 bytes) concatenated, then split at offsets that land
+        // inside a multi-byte character on both sides of the split.
+        let whole = "café日本語Привет".as_bytes();
+        let splits = [3, 5, 6, 9, 12, 15, 17, 19, 21];
+
+        for &split in &splits {
+            let parts: [&[u8]; 2] = [&whole[..split], &whole[split..]];
+            assert_eq!(validate_utf8_vectored(&parts), Ok(()), "split at {split}");
+        }
+
+        let parts: Vec<&[u8]> = whole.chunks(2).collect();
+        assert_eq!(validate_utf8_vectored(&parts), Ok(()));
+    }
+
+    #[test]
+    fn vectored_reports_absolute_offset_of_error() {
+        use super::validate_utf8_vectored;
+
+        let parts: [&[u8]; 3] = [b"hello ", b"wor", b"ld\xFF"];
+        let err = validate_utf8_vectored(&parts).unwrap_err();
+        assert_eq!(
+            err,
+            super::Utf8Error { valid_up_to: 11, error_len: Some(1), error_byte: Some(0xFF) }
+        );
+    }
+
+    #[test]
+    fn slices_reports_error_in_concatenated_coordinate_space() {
+        use super::validate_utf8_slices;
+
+        let parts: [&[u8]; 3] = [b"hello ", b"wor", b"ld\xFF"];
+        let err = validate_utf8_slices(&parts).unwrap_err();
+        assert_eq!(
+            err,
+            super::Utf8Error { valid_up_to: 11, error_len: Some(1), error_byte: Some(0xFF) }
+        );
+    }
+
+    #[test]
+    fn slices_rejects_stream_left_incomplete() {
+        use super::validate_utf8_slices;
+
+        let parts: [&[u8]; 2] = [b"hi ", b"\xE2\x82"];
+        let err = validate_utf8_slices(&parts).unwrap_err();
+        assert_eq!(err, super::Utf8Error { valid_up_to: 3, error_len: None, error_byte: None });
+    }
+
+    #[test]
+    fn from_reader_accepts_valid_utf8_split_across_reads() {
+        use super::validate_utf8_from_reader;
+        use std::io::Cursor;
+
+        let text = "hi caf\u{e9}, 日本語!".as_bytes();
+        let cursor = Cursor::new(text);
+        assert_eq!(validate_utf8_from_reader(cursor).unwrap(), Ok(()));
+    }
+
+    #[test]
+    fn from_reader_reports_absolute_offset_of_mid_stream_error() {
+        use super::validate_utf8_from_reader;
+        use std::io::Cursor;
+
+        let mut stream = b"hello world, ".to_vec();
+        stream.extend_from_slice("café".as_bytes());
+        stream.push(0xFF);
+        stream.extend_from_slice(b" more text after the error");
+
+        let expected_valid_up_to = b"hello world, ".len() + "café".len();
+        let cursor = Cursor::new(stream);
+        let err = validate_utf8_from_reader(cursor).unwrap().unwrap_err();
+        assert_eq!(
+            err,
+            super::Utf8Error { valid_up_to: expected_valid_up_to, error_len: Some(1), error_byte: Some(0xFF) }
+        );
+    }
+
+    #[test]
+    fn checkpoint_accumulates_total_bytes_across_calls() {
+        use super::{validate_utf8_with_checkpoint, Checkpoint};
+
+        let mut cp = Checkpoint::new();
+        assert!(validate_utf8_with_checkpoint(b"hello ", &mut cp).is_ok());
+        assert!(validate_utf8_with_checkpoint(b"world", &mut cp).is_ok());
+        assert_eq!(cp.total_bytes, 11);
+        assert!(cp.is_complete());
+    }
+
+    #[test]
+    fn checkpoint_handles_char_split_across_calls() {
+        use super::{validate_utf8_with_checkpoint, Checkpoint};
+
+        let mut cp = Checkpoint::new();
+        assert!(validate_utf8_with_checkpoint(b"caf\xC3", &mut cp).is_ok());
+        assert!(!cp.is_complete());
+        assert!(validate_utf8_with_checkpoint(b"\xA9", &mut cp).is_ok());
+        assert!(cp.is_complete());
+    }
+
+    #[test]
+    fn checkpoint_reports_error_at_absolute_offset() {
+        use super::{validate_utf8_with_checkpoint, Checkpoint};
+
+        let mut cp = Checkpoint::new();
+        assert!(validate_utf8_with_checkpoint(b"hello ", &mut cp).is_ok());
+        let err = validate_utf8_with_checkpoint(b"wor\xFFld", &mut cp).unwrap_err();
+        assert_eq!(
+            err,
+            super::Utf8Error { valid_up_to: 9, error_len: Some(1), error_byte: Some(0xFF) }
+        );
+    }
+
+    #[test]
+    fn from_utf8_accepts_valid_input_and_rejects_invalid() {
+        use super::{from_utf8, validate_utf8};
+
+        assert_eq!(from_utf8(b"caf\xC3\xA9").unwrap(), "café");
+        assert_eq!(from_utf8(b"ab\xFFcd").unwrap_err(), validate_utf8(b"ab\xFFcd").unwrap_err());
+    }
+
+    #[test]
+    fn trim_removes_ascii_whitespace_only() {
+        use super::validate_and_trim;
+
+        assert_eq!(validate_and_trim(b"  hello world  \n").unwrap(), "hello world");
+        assert_eq!(validate_and_trim(b" caf\xC3\xA9 ").unwrap(), "café");
+        assert_eq!(validate_and_trim(b"").unwrap(), "");
+    }
+
+    #[test]
+    fn visit_reports_ascii_runs_and_chars() {
+        use super::{validate_utf8_visit, Utf8Error, Utf8Visitor};
+
+        #[derive(Default)]
+        struct Collector {
+            runs: Vec<Vec<u8>>,
+            chars: Vec<char>,
+            error: Option<Utf8Error>,
+        }
+
+        impl Utf8Visitor for Collector {
+            fn on_ascii_run(&mut self, run: &[u8]) {
+                self.runs.push(run.to_vec());
+            }
+
+            fn on_char(&mut self, ch: char) {
+                self.chars.push(ch);
+            }
+
+            fn on_error(&mut self, err: Utf8Error) {
+                self.error = Some(err);
+            }
+        }
+
+        let mut collector = Collector::default();
+        validate_utf8_visit("ab café".as_bytes(), &mut collector);
+        assert_eq!(collector.runs, vec![b"ab caf".to_vec()]);
+        assert_eq!(collector.chars, vec!['é']);
+        assert_eq!(collector.error, None);
+    }
+
+    #[test]
+    fn visit_reports_error() {
+        use super::{validate_utf8_visit, Utf8Visitor};
+
+        #[derive(Default)]
+        struct ErrorOnly(bool);
+        impl Utf8Visitor for ErrorOnly {
+            fn on_error(&mut self, _err: super::Utf8Error) {
+                self.0 = true;
+            }
+        }
+
+        let mut v = ErrorOnly::default();
+        validate_utf8_visit(b"A\xC3\xA9 \xF1 ", &mut v);
+        assert!(v.0);
+    }
+
+    #[test]
+    fn count_codepoints_matches_chars_count() {
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+
+        for s in ["", "hello", "café", "p\u{0430}ypal.com", CHINESE] {
+            let expected = s.chars().count();
+            assert_eq!(
+                super::validate_utf8_count_codepoints(s.as_bytes()),
+                Ok(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn count_chars_matches_chars_count_for_mixed_scripts() {
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+
+        for s in ["", "hello", "café", CHINESE] {
+            let expected = s.chars().count();
+            assert_eq!(super::validate_and_count_chars(s.as_bytes()), Ok(expected));
+        }
+
+        // char count should differ substantially from byte count for
+        // multi-byte-heavy text, not just happen to match
+        assert!(CHINESE.chars().count() < CHINESE.len() / 2);
+    }
+
+    #[test]
+    fn count_codepoints_rejects_invalid_utf8() {
+        let err = super::validate_utf8_count_codepoints(b"A\xC3\xA9 \xF1 ").unwrap_err();
+        assert_eq!(err, super::validate_utf8(b"A\xC3\xA9 \xF1 ").unwrap_err());
+    }
+
+    #[test]
+    fn sniff_detects_utf8() {
+        use super::EncodingHint;
+
+        assert_eq!(super::sniff_encoding("café".as_bytes()), EncodingHint::Utf8);
+        assert_eq!(
+            super::sniff_encoding(&[0xEF, 0xBB, 0xBF, b'h', b'i']),
+            EncodingHint::Utf8
+        );
+    }
+
+    #[test]
+    fn sniff_detects_utf16_variants() {
+        use super::EncodingHint;
+
+        assert_eq!(
+            super::sniff_encoding(&[0xFF, 0xFE, b'h', 0, b'i', 0]),
+            EncodingHint::Utf16Le
+        );
+        assert_eq!(
+            super::sniff_encoding(&[0xFE, 0xFF, 0, b'h', 0, b'i']),
+            EncodingHint::Utf16Be
+        );
+
+        // no BOM, but a dense alternating-NUL pattern
+        let le: Vec<u8> = "hello world".bytes().flat_map(|b| [b, 0]).collect();
+        assert_eq!(super::sniff_encoding(&le), EncodingHint::Utf16Le);
+
+        let be: Vec<u8> = "hello world".bytes().flat_map(|b| [0, b]).collect();
+        assert_eq!(super::sniff_encoding(&be), EncodingHint::Utf16Be);
+    }
+
+    #[test]
+    fn sniff_falls_back_to_latin1_or_unknown() {
+        use super::EncodingHint;
+
+        // 0xE9 alone is invalid UTF-8, has no NULs -> plausible Latin-1
+        assert_eq!(super::sniff_encoding(&[b'h', b'i', 0xE9]), EncodingHint::Latin1);
+
+        // invalid UTF-8 with a stray NUL not in any recognizable pattern
+        assert_eq!(super::sniff_encoding(&[0xE9, 0]), EncodingHint::Unknown);
+    }
+
+    #[test]
+    fn describe_error_copies_bad_bytes() {
+        let buf = b"ab\xF1\x80\x80";
+        let err = super::validate_utf8(buf).unwrap_err();
+        let detail = super::describe_error(buf, &err);
+        assert_eq!(detail.valid_up_to, 2);
+        assert_eq!(detail.error_len, None);
+        assert_eq!(detail.bad_bytes(), &buf[2..]);
+    }
+
+    #[test]
+    fn describe_error_truncates_to_four_bytes() {
+        let buf = b"\xFF\xFF\xFF\xFF\xFF\xFF";
+        let err = super::validate_utf8(buf).unwrap_err();
+        let detail = super::describe_error(buf, &err);
+        assert_eq!(detail.bad_bytes().len(), 4);
+        assert_eq!(detail.bad_bytes(), &buf[..4]);
+    }
+
+    #[test]
+    fn chunked_validation_agrees_with_default_on_valid_input() {
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+        assert_eq!(
+            super::validate_utf8_parallel_chunked_with_correct_offsets(CHINESE.as_bytes(), 8),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn chunked_validation_finds_lowest_offset_error_across_chunks() {
+        let mut buf = vec![b'a'; 64];
+        // plant errors in what will become chunk 1 (offset 20) and
+        // chunk 2 (offset 40) once split into 4 chunks of 16 bytes each
+        buf[40] = 0xFF;
+        buf[20] = 0xFF;
+
+        let err = super::validate_utf8_parallel_chunked_with_correct_offsets(&buf, 4).unwrap_err();
+        assert_eq!(err.valid_up_to, 20);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_agrees_with_default_on_valid_input() {
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+        assert_eq!(super::validate_utf8_parallel(CHINESE.as_bytes()), Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_reports_same_valid_up_to_as_serial_validator() {
+        // one megabyte, large enough to actually split across multiple
+        // rayon threads, with a single planted error roughly a third in
+        let mut buf = vec![b'a'; 1 << 20];
+        let bad_at = buf.len() / 3;
+        buf[bad_at] = 0xFF;
+
+        let parallel_err = super::validate_utf8_parallel(&buf).unwrap_err();
+        let serial_err = super::validate_utf8(&buf).unwrap_err();
+        assert_eq!(parallel_err, serial_err);
+        assert_eq!(parallel_err.valid_up_to, bad_at);
+    }
+
+    #[test]
+    fn error_predicates_distinguish_incomplete_from_invalid() {
+        let incomplete = super::validate_utf8(b"caf\xC3").unwrap_err();
+        assert!(incomplete.is_incomplete());
+        assert!(!incomplete.is_invalid());
+        assert_eq!(incomplete.error_len(), None);
+        assert_eq!(incomplete.valid_up_to(), 3);
+
+        let invalid = super::validate_utf8(b"\xFF").unwrap_err();
+        assert!(invalid.is_invalid());
+        assert!(!invalid.is_incomplete());
+        assert_eq!(invalid.error_len(), Some(1));
+        assert_eq!(invalid.valid_up_to(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "reference-impl")]
+    fn std_with_stats_agrees_with_stats_free_on_malformed_input() {
+        use super::{validate_utf8_std, validate_utf8_std_with_stats, Statistics};
+
+        let buf = b"hello \xC3\xA9 world \xFF trailing";
+
+        let plain = validate_utf8_std(buf);
+        let mut stats = Statistics::default();
+        let instrumented = validate_utf8_std_with_stats(buf, Some(&mut stats));
+
+        assert_eq!(plain, instrumented);
+        let err = instrumented.unwrap_err();
+        assert_eq!(err.valid_up_to, plain.unwrap_err().valid_up_to);
+        assert!(stats.bytewise_checks > 0);
+    }
+
+    #[test]
+    fn display_matches_std_message_wording() {
+        let incomplete = super::validate_utf8(b"caf\xC3").unwrap_err();
+        assert_eq!(incomplete.to_string(), "incomplete utf-8 byte sequence from index 3");
+
+        let invalid = super::validate_utf8(b"ab\xFFcd").unwrap_err();
+        assert_eq!(invalid.to_string(), "invalid utf-8 sequence of 1 bytes from index 2");
+    }
+
+    #[test]
+    fn implements_core_error() {
+        fn assert_error<E: core::error::Error>(_: &E) {}
+        assert_error(&super::validate_utf8(b"\xFF").unwrap_err());
+    }
+
+    #[test]
+    fn last_char_boundary_ascii_tail_is_its_own_last_byte() {
+        assert_eq!(super::last_char_boundary(b"hello"), 4);
+    }
+
+    #[test]
+    fn last_char_boundary_finds_start_of_complete_multibyte_char() {
+        let buf = "café".as_bytes();
+        assert_eq!(super::last_char_boundary(buf), buf.len() - 2);
+    }
+
+    #[test]
+    fn last_char_boundary_finds_start_of_truncated_char() {
+        // a 4-byte emoji with only the lead byte and one continuation byte present
+        let full = "a😀".as_bytes();
+        let truncated = &full[..full.len() - 2];
+        assert_eq!(super::last_char_boundary(truncated), 1);
+    }
+
+    #[test]
+    fn last_char_boundary_caps_backward_scan_at_three_bytes() {
+        let buf = [0x80, 0x80, 0x80, 0x80];
+        assert_eq!(super::last_char_boundary(&buf), 0);
+    }
+
+    #[test]
+    fn find_char_boundary_before_matches_a_naive_floor_char_boundary_at_every_offset() {
+        use super::find_char_boundary_before;
+
+        // a multi-script buffer mixing 1/2/3/4-byte characters
+        let s = "a\u{e9}\u{4e2d}\u{1f600}b";
+        let buf = s.as_bytes();
+
+        // `str::floor_char_boundary` is nightly-only, so this reference
+        // walks `char_indices` to find the largest boundary `<= index`
+        let floor_char_boundary = |index: usize| -> usize {
+            s.char_indices()
+                .map(|(i, _)| i)
+                .chain(core::iter::once(s.len()))
+                .filter(|&i| i <= index)
+                .max()
+                .unwrap()
+        };
+
+        for index in 0..=buf.len() {
+            assert_eq!(
+                find_char_boundary_before(buf, index),
+                floor_char_boundary(index),
+                "mismatch at index {index}"
+            );
+        }
+    }
+
+    #[test]
+    fn char_indices_fast_agrees_with_std_char_indices_on_bundled_german_text() {
+        use super::char_indices_fast;
+
+        let expected: Vec<(usize, char)> = GERMAN_UTF8_16KB.char_indices().collect();
+        let actual: Vec<(usize, char)> = char_indices_fast(GERMAN_UTF8_16KB.as_bytes()).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn find_char_boundary_before_clamps_an_index_past_the_end() {
+        use super::find_char_boundary_before;
+
+        let buf = "caf\u{e9}".as_bytes();
+        assert_eq!(find_char_boundary_before(buf, buf.len() + 5), buf.len());
+    }
+
+    #[test]
+    fn validate_and_hash_is_deterministic_and_sensitive_to_input() {
+        use std::hash::{DefaultHasher, Hasher};
+
+        let buf = "hello, café world — this is over sixteen ASCII bytes".as_bytes();
+
+        let mut h1 = DefaultHasher::new();
+        assert_eq!(super::validate_and_hash(buf, &mut h1), Ok(()));
+        let mut h2 = DefaultHasher::new();
+        assert_eq!(super::validate_and_hash(buf, &mut h2), Ok(()));
+        assert_eq!(h1.finish(), h2.finish());
+
+        let mut h3 = DefaultHasher::new();
+        assert_eq!(
+            super::validate_and_hash(b"a completely different input", &mut h3),
+            Ok(())
+        );
+        assert_ne!(h1.finish(), h3.finish());
+    }
+
+    #[test]
+    fn validate_and_hash_reports_error_with_correct_offset() {
+        use std::hash::DefaultHasher;
+
+        let buf = b"0123456789ABCDEF\xFF";
+        let mut hasher = DefaultHasher::new();
+        let err = super::validate_and_hash(buf, &mut hasher).unwrap_err();
+        assert_eq!(
+            err,
+            super::Utf8Error { valid_up_to: 16, error_len: Some(1), error_byte: Some(0xFF) }
+        );
+    }
+
+    #[test]
+    fn validate_utf8_array_agrees_with_slice_validator() {
+        let buf8: [u8; 8] = *b"abcdefgh";
+        let buf16: [u8; 16] = *b"abcdefghijklmnop";
+        let mut buf32 = [b'x'; 32];
+        buf32[10] = 0xFF;
+
+        assert_eq!(super::validate_utf8_array(&buf8), super::validate_utf8(&buf8));
+        assert_eq!(super::validate_utf8_array(&buf16), super::validate_utf8(&buf16));
+        assert_eq!(super::validate_utf8_array(&buf32), super::validate_utf8(&buf32));
+    }
+
+    #[test]
+    fn raw_agrees_with_slice_validator() {
+        use super::validate_utf8_raw;
+
+        let valid: Vec<u8> = "hello, café, 日本語".into();
+        // SAFETY: `valid` outlives the call, and its length matches `len`
+        let result = unsafe { validate_utf8_raw(valid.as_ptr(), valid.len()) };
+        assert_eq!(result, super::validate_utf8(&valid));
+
+        let invalid: Vec<u8> = b"ab\xFF".to_vec();
+        // SAFETY: `invalid` outlives the call, and its length matches `len`
+        let result = unsafe { validate_utf8_raw(invalid.as_ptr(), invalid.len()) };
+        assert_eq!(result, super::validate_utf8(&invalid));
+    }
+
+    #[test]
+    fn char_boundaries_collapses_ascii_runs() {
+        use super::CharBoundary;
+
+        let mut out = Vec::new();
+        super::validate_utf8_char_boundaries("ab café cd".as_bytes(), &mut out).unwrap();
+        assert_eq!(
+            out,
+            vec![
+                CharBoundary::AsciiRun { start: 0, len: 6 },
+                CharBoundary::Lead(6),
+                CharBoundary::AsciiRun { start: 8, len: 3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn char_boundaries_reports_error() {
+        let mut out = Vec::new();
+        let err = super::validate_utf8_char_boundaries(b"ab\xFF", &mut out).unwrap_err();
+        assert_eq!(err, super::validate_utf8(b"ab\xFF").unwrap_err());
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn no_bidi_controls_accepts_plain_text() {
+        assert_eq!(super::validate_utf8_no_bidi_controls("café".as_bytes()), Ok(()));
+    }
+
+    #[test]
+    fn no_bidi_controls_rejects_rtl_override() {
+        let s = format!("a{}b", '\u{202E}');
+        let err = super::validate_utf8_no_bidi_controls(s.as_bytes()).unwrap_err();
+        assert_eq!(err.valid_up_to, 1);
+        assert_eq!(err.error_len, Some(3));
+        // the rejected sequence was read in full, not truncated, so
+        // `error_byte` must be `Some` (the character's lead byte)
+        assert_eq!(err.error_byte, Some('\u{202E}'.encode_utf8(&mut [0u8; 4]).as_bytes()[0]));
+    }
+
+    #[test]
+    fn no_bidi_controls_still_reports_real_utf8_errors() {
+        let err = super::validate_utf8_no_bidi_controls(b"ab\xFF").unwrap_err();
+        assert_eq!(err, super::validate_utf8(b"ab\xFF").unwrap_err());
+    }
+
+    #[test]
+    fn count_combining_ignores_plain_ascii() {
+        assert_eq!(super::validate_utf8_count_combining(b"hello").unwrap(), 0);
+    }
+
+    #[test]
+    fn count_combining_counts_combining_diacritics() {
+        // "e" + COMBINING ACUTE ACCENT (U+0301), twice
+        let buf = "e\u{0301}e\u{0301}".as_bytes();
+        assert_eq!(super::validate_utf8_count_combining(buf).unwrap(), 2);
+    }
+
+    #[test]
+    fn count_combining_rejects_invalid_utf8() {
+        assert!(super::validate_utf8_count_combining(b"ab\xFF").is_err());
+    }
+
+    #[test]
+    fn categorize_marks_ascii_lead_and_continuation_bytes() {
+        use super::{validate_and_categorize, CATEGORY_ASCII, CATEGORY_CONT, CATEGORY_LEAD};
+
+        let buf = "aé".as_bytes(); // ['a', 0xC3, 0xA9]
+        let mut out = vec![0u8; buf.len()];
+        validate_and_categorize(buf, &mut out).unwrap();
+        assert_eq!(out, [CATEGORY_ASCII, CATEGORY_LEAD, CATEGORY_CONT]);
+    }
+
+    #[test]
+    fn categorize_reports_error_and_leaves_out_untouched() {
+        use super::validate_and_categorize;
+
+        let buf = b"ab\xFF";
+        let mut out = vec![0xAAu8; buf.len()];
+        let err = validate_and_categorize(buf, &mut out).unwrap_err();
+        assert_eq!(err, super::validate_utf8(buf).unwrap_err());
+        assert_eq!(out, [0xAA, 0xAA, 0xAA]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn categorize_panics_on_length_mismatch() {
+        use super::validate_and_categorize;
+
+        let mut out = vec![0u8; 1];
+        let _ = validate_and_categorize(b"ab", &mut out);
+    }
+
+    #[test]
+    fn statistics_csv_row_matches_header_column_count() {
+        let stats = super::Statistics {
+            success_blocks_8x: 1,
+            failed_blocks_8x: 2,
+            success_blocks_4x: 3,
+            failed_blocks_4x: 4,
+            success_blocks_2x: 5,
+            failed_blocks_2x: 6,
+            unaligned_blocks: 7,
+            bytewise_checks: 8,
+            non_ascii_checks: 9,
+            optimistic_2x_to_8x: 10,
+        };
+        assert_eq!(
+            super::Statistics::csv_header().split(',').count(),
+            stats.to_csv_row().split(',').count()
+        );
+        assert_eq!(stats.to_csv_row(), "1,2,3,4,5,6,7,8,9,10");
+    }
+
+    #[test]
+    fn statistics_merge_adds_counters_field_wise() {
+        let mut total = super::Statistics {
+            success_blocks_8x: 1,
+            failed_blocks_8x: 2,
+            success_blocks_4x: 3,
+            failed_blocks_4x: 4,
+            success_blocks_2x: 5,
+            failed_blocks_2x: 6,
+            unaligned_blocks: 7,
+            bytewise_checks: 8,
+            non_ascii_checks: 9,
+            optimistic_2x_to_8x: 10,
+        };
+        let chunk = super::Statistics {
+            success_blocks_8x: 10,
+            failed_blocks_8x: 20,
+            success_blocks_4x: 30,
+            failed_blocks_4x: 40,
+            success_blocks_2x: 50,
+            failed_blocks_2x: 60,
+            unaligned_blocks: 70,
+            bytewise_checks: 80,
+            non_ascii_checks: 90,
+            optimistic_2x_to_8x: 100,
+        };
+
+        total.merge(&chunk);
+
+        assert_eq!(total.to_csv_row(), "11,22,33,44,55,66,77,88,99,110");
+        assert_eq!(total.success_ratio_8x(), 11.0 / 33.0);
+        assert_eq!(total.success_ratio_4x(), 33.0 / 77.0);
+        assert_eq!(total.success_ratio_2x(), 55.0 / 121.0);
+    }
+
+    #[test]
+    fn statistics_reset_zeroes_all_counters() {
+        let mut stats = super::Statistics {
+            success_blocks_8x: 1,
+            failed_blocks_8x: 2,
+            success_blocks_4x: 3,
+            failed_blocks_4x: 4,
+            success_blocks_2x: 5,
+            failed_blocks_2x: 6,
+            unaligned_blocks: 7,
+            bytewise_checks: 8,
+            non_ascii_checks: 9,
+            optimistic_2x_to_8x: 10,
+        };
+
+        stats.reset();
+
+        assert_eq!(stats.to_csv_row(), "0,0,0,0,0,0,0,0,0,0");
+    }
+
+    #[test]
+    fn bytes_scanned_fast_dominates_for_mostly_ascii_text() {
+        use super::{validate_utf8_with_stats, Statistics};
+
+        const ENGLISH: &str = include_str!("../assets/english_971kb.txt");
+        let mut stats = Statistics::default();
+        assert!(validate_utf8_with_stats(ENGLISH.as_bytes(), Some(&mut stats)).is_ok());
+
+        let fast = stats.bytes_scanned_fast();
+        let bytewise = stats.bytes_scanned_bytewise();
+        let fraction = fast as f64 / (fast + bytewise) as f64;
+        assert!(fraction > 0.95, "fast-scanned fraction was only {fraction}");
+    }
+
+    #[test]
+    fn with_stats_populates_success_blocks_4x_between_8x_and_2x() {
+        use super::{validate_utf8_with_stats, word_bytes, Statistics};
+
+        // sized so the 8x loop consumes exactly one 8-word block and then
+        // exits with exactly one 4-word block's worth of ASCII left, which
+        // only the 4x tier (and not the 2x loop) should account for
+        let buf = vec![b'a'; 8 * word_bytes() + 4 * word_bytes()];
+
+        let mut stats = Statistics::default();
+        assert!(validate_utf8_with_stats(&buf, Some(&mut stats)).is_ok());
+
+        assert_eq!(stats.success_blocks_8x, 1);
+        assert_eq!(stats.success_blocks_4x, 1);
+        assert_eq!(stats.failed_blocks_4x, 0);
+    }
+
+    #[test]
+    fn with_stats_reports_failed_blocks_4x_for_non_ascii_in_4x_window() {
+        use super::{validate_utf8_with_stats, word_bytes, Statistics};
+
+        // one full 8-word block of ASCII, followed by a 4-word block whose
+        // first byte is non-ASCII: the 8x loop should succeed once, and the
+        // 4x tier should be the one to catch the non-ASCII byte
+        let mut buf = vec![b'a'; 8 * word_bytes() + 4 * word_bytes()];
+        buf[8 * word_bytes()] = 0xC3;
+        buf[8 * word_bytes() + 1] = 0xA9;
+
+        let mut stats = Statistics::default();
+        assert!(validate_utf8_with_stats(&buf, Some(&mut stats)).is_ok());
+
+        assert_eq!(stats.success_blocks_8x, 1);
+        assert_eq!(stats.failed_blocks_4x, 1);
+    }
+
+    #[test]
+    fn word_bytes_matches_usize_size() {
+        assert_eq!(super::word_bytes(), core::mem::size_of::<usize>());
+    }
+
+    #[test]
+    fn auto_diag_reports_portable_backend_and_agrees_with_validate_utf8() {
+        use super::validate_utf8_auto_diag;
+
+        let text = "a".repeat(4096);
+        let (result, diag) = validate_utf8_auto_diag(text.as_bytes());
+        assert_eq!(result, Ok(()));
+        assert_eq!(diag.backend, "portable");
+        assert!(diag.vectors_processed > 0);
+    }
+
+    #[test]
+    fn auto_diag_still_reports_the_error() {
+        use super::validate_utf8_auto_diag;
+
+        let (result, _diag) = validate_utf8_auto_diag(b"ab\xFF");
+        assert_eq!(result, super::validate_utf8(b"ab\xFF"));
+    }
+
+    #[test]
+    fn replacement_plan_is_empty_for_valid_utf8() {
+        assert_eq!(
+            super::validate_utf8_returning_replacement_plan(b"hello, cafe"),
+            []
+        );
+    }
+
+    #[test]
+    fn replacement_plan_groups_one_bad_lead_byte_per_char() {
+        let buf = b"a\xFFb\xFEc";
+        let plan = super::validate_utf8_returning_replacement_plan(buf);
+        assert_eq!(
+            plan,
+            [
+                (1..2, char::REPLACEMENT_CHARACTER),
+                (3..4, char::REPLACEMENT_CHARACTER),
+            ]
+        );
+    }
+
+    #[test]
+    fn replacement_plan_covers_truncated_trailing_sequence() {
+        let buf = b"hi \xE2\x82"; // truncated 3-byte sequence
+        let plan = super::validate_utf8_returning_replacement_plan(buf);
+        assert_eq!(plan, [(3..5, char::REPLACEMENT_CHARACTER)]);
+    }
+
+    #[test]
+    fn replacement_plan_matches_from_utf8_lossy_char_count() {
+        let buf = b"a\xFFb\xC0\x80c";
+        let plan = super::validate_utf8_returning_replacement_plan(buf);
+        let lossy = String::from_utf8_lossy(buf);
+        let replacement_chars = lossy.chars().filter(|&c| c == char::REPLACEMENT_CHARACTER).count();
+        assert_eq!(plan.len(), replacement_chars);
+    }
+
+    #[test]
+    fn is_ascii_matches_stdlib_predicate_on_bundled_assets() {
+        use super::is_ascii;
+
+        let assets: &[&[u8]] = &[
+            b"",
+            b"the quick brown fox jumps over the lazy dog",
+            GERMAN_UTF8_16KB.as_bytes(),
+            include_bytes!("../assets/chinese_1mb.txt"),
+            include_bytes!("../assets/japanese_203kb.txt"),
+            include_bytes!("../assets/greek_57kb.txt"),
+            include_bytes!("../assets/hungarian_52kb.txt"),
+        ];
+
+        for asset in assets {
+            assert_eq!(is_ascii(asset), asset.iter().all(u8::is_ascii));
+        }
+    }
+
+    #[test]
+    fn is_ascii_finds_non_ascii_byte_at_every_block_alignment() {
+        use super::is_ascii;
+
+        let word = super::WORD_BYTES;
+        for len in [1, word - 1, word, word + 1, 2 * word, 8 * word, 8 * word + word / 2] {
+            for bad_at in 0..len {
+                let mut buf = vec![b'a'; len];
+                buf[bad_at] = 0xFF;
+                assert!(!is_ascii(&buf), "len {len}, bad byte at {bad_at}");
+            }
+            assert!(is_ascii(&vec![b'a'; len]), "len {len} should be all-ASCII");
+        }
+    }
+
+    #[test]
+    fn ascii_first_non_ascii_is_none_for_all_ascii() {
+        use super::ascii::first_non_ascii;
+
+        assert_eq!(first_non_ascii(b""), None);
+        assert_eq!(first_non_ascii(b"the quick brown fox"), None);
+    }
+
+    #[test]
+    fn ascii_first_non_ascii_finds_byte_in_first_middle_and_last_word_of_block() {
+        use super::ascii::first_non_ascii;
+
+        // a 2-word (16-byte on a 64-bit target) block, big enough to
+        // exercise the first, middle, and last `usize`-sized word
+        let word = super::WORD_BYTES;
+        let mut buf = vec![b'a'; 2 * word];
+
+        buf[0] = 0xFF;
+        assert_eq!(first_non_ascii(&buf), Some(0));
+
+        let mut buf = vec![b'a'; 2 * word];
+        buf[word] = 0xFF;
+        assert_eq!(first_non_ascii(&buf), Some(word));
+
+        let mut buf = vec![b'a'; 2 * word];
+        buf[2 * word - 1] = 0xFF;
+        assert_eq!(first_non_ascii(&buf), Some(2 * word - 1));
+    }
+
+    #[test]
+    fn ascii_first_non_ascii_handles_unaligned_starts() {
+        use super::ascii::first_non_ascii;
+
+        let word = super::WORD_BYTES;
+        let mut buf = vec![b'a'; 4 * word];
+        buf[word / 2] = 0xFF;
+
+        // slicing off a few leading bytes forces `buf.as_ptr()` to start
+        // unaligned relative to `WORD_BYTES`, exercising the byte-wise
+        // prefix walk before the word-block loop takes over
+        for offset in 1..word {
+            let sliced = &buf[offset..];
+            let expected = if word / 2 >= offset { Some(word / 2 - offset) } else { None };
+            assert_eq!(first_non_ascii(sliced), expected, "offset {offset}");
+        }
+    }
+
+    #[test]
+    fn first_non_ascii_offset_is_none_for_pure_ascii() {
+        use super::first_non_ascii_offset;
+
+        assert_eq!(first_non_ascii_offset(b""), None);
+        assert_eq!(first_non_ascii_offset(b"GET /index HTTP/1.1"), None);
+    }
+
+    #[test]
+    fn first_non_ascii_offset_finds_the_boundary_of_an_ascii_header() {
+        use super::first_non_ascii_offset;
+
+        let buf = [b"GET /", &b"\xC3\xA9caf\xC3\xA9"[..]].concat();
+        assert_eq!(first_non_ascii_offset(&buf), Some(5));
+    }
+
+    #[test]
+    fn first_non_ascii_offset_does_not_validate_the_multi_byte_grammar() {
+        use super::first_non_ascii_offset;
+
+        // `\xFF` can never start a valid UTF-8 sequence, but this function
+        // only cares that it's `>= 0x80`
+        let buf = b"ascii\xFF";
+        assert_eq!(first_non_ascii_offset(buf), Some(5));
+    }
+
+    #[test]
+    fn configured_agrees_with_default_validator_on_bundled_assets() {
+        use super::{validate_utf8_configured, Utf8Config};
+
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+        const ENGLISH: &str = include_str!("../assets/english_971kb.txt");
+
+        for cfg in [
+            Utf8Config { primary_block_words: 4, secondary_block_words: 1 },
+            Utf8Config { primary_block_words: 16, secondary_block_words: 4 },
+        ] {
+            assert!(validate_utf8_configured(CHINESE.as_bytes(), &cfg).is_ok());
+            assert!(validate_utf8_configured(ENGLISH.as_bytes(), &cfg).is_ok());
+        }
+    }
+
+    #[test]
+    fn configured_reports_same_valid_up_to_as_default_validator() {
+        use super::{validate_utf8_configured, Utf8Config};
+
+        let word = super::WORD_BYTES;
+        let mut buf = vec![b'a'; 20 * word];
+        buf[10 * word] = 0xFF;
+
+        for cfg in [
+            Utf8Config { primary_block_words: 4, secondary_block_words: 1 },
+            Utf8Config { primary_block_words: 16, secondary_block_words: 4 },
+        ] {
+            let expected = validate_utf8(&buf).unwrap_err();
+            let actual = validate_utf8_configured(&buf, &cfg).unwrap_err();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "primary_block_words >= secondary_block_words >= 1")]
+    fn configured_rejects_secondary_wider_than_primary() {
+        use super::{validate_utf8_configured, Utf8Config};
+
+        let _ = validate_utf8_configured(b"abc", &Utf8Config { primary_block_words: 1, secondary_block_words: 2 });
+    }
+
+    #[test]
+    fn from_utf8_lossy_borrows_when_input_is_already_valid() {
+        use super::from_utf8_lossy;
+
+        let buf = b"caf\xC3\xA9";
+        assert!(matches!(from_utf8_lossy(buf), std::borrow::Cow::Borrowed(_)));
+        assert_eq!(from_utf8_lossy(buf), "café");
+    }
+
+    #[test]
+    fn from_utf8_lossy_matches_std_on_malformed_input() {
+        use super::from_utf8_lossy;
+
+        let inputs: &[&[u8]] = &[
+            b"a\xFFb\xFEc",
+            b"hi \xE2\x82",
+            b"a\xFFb\xC0\x80c",
+            b"\xF4\x90\x80\x80",
+            b"",
+        ];
+
+        for buf in inputs {
+            assert_eq!(from_utf8_lossy(buf), String::from_utf8_lossy(buf));
+        }
+    }
+
+    #[test]
+    fn utf8_or_latin1_to_string_passes_through_valid_utf8() {
+        use super::utf8_or_latin1_to_string;
+
+        assert_eq!(utf8_or_latin1_to_string("café".as_bytes()), "café");
+    }
+
+    #[test]
+    fn utf8_or_latin1_to_string_decodes_invalid_utf8_as_latin1() {
+        use super::utf8_or_latin1_to_string;
+
+        // 0xE9 is invalid as a UTF-8 lead byte here, but as Latin-1 it is
+        // U+00E9 (é).
+        assert_eq!(utf8_or_latin1_to_string(b"caf\xE9"), "café");
+    }
+
+    #[test]
+    fn utf8_or_latin1_to_string_round_trips_every_byte_value() {
+        use super::utf8_or_latin1_to_string;
+
+        let buf: Vec<u8> = (0u8..=255).collect();
+        let s = utf8_or_latin1_to_string(&buf);
+        let chars: Vec<char> = s.chars().collect();
+        assert_eq!(chars.len(), 256);
+        for (byte, ch) in buf.iter().zip(chars) {
+            assert_eq!(ch as u32, *byte as u32);
+        }
+    }
+
+    #[test]
+    fn error_len_one_for_stray_continuation_and_invalid_leads() {
+        use super::validate_utf8_strict;
+
+        for &buf in &[&b"\x80"[..], b"\xBF", b"\xC0", b"\xC1", b"\xF5", b"\xFF"] {
+            let err = validate_utf8_strict(buf).unwrap_err();
+            assert_eq!(err.valid_up_to, 0);
+            assert_eq!(err.error_len, Some(1));
+        }
+    }
+
+    #[test]
+    fn error_len_one_for_overlong_and_surrogate_first_continuation_byte() {
+        use super::validate_utf8_strict;
+
+        // Overlong two-byte, overlong three-byte, overlong four-byte, and a
+        // UTF-16 surrogate half all break the grammar at the very first
+        // continuation byte.
+        for &buf in &[&b"\xC0\x80"[..], b"\xE0\x80\x80", b"\xF0\x80\x80\x80", b"\xED\xA0\x80"] {
+            let err = validate_utf8_strict(buf).unwrap_err();
+            assert_eq!(err.valid_up_to, 0);
+            assert_eq!(err.error_len, Some(1));
+        }
+    }
+
+    #[test]
+    fn error_len_two_for_break_after_one_good_continuation_byte() {
+        use super::validate_utf8_strict;
+
+        // A legal three-byte lead and first continuation byte, then a byte
+        // that isn't a continuation byte at all.
+        let err = validate_utf8_strict(b"\xE2\x82\x28").unwrap_err();
+        assert_eq!(err.valid_up_to, 0);
+        assert_eq!(err.error_len, Some(2));
+    }
+
+    #[test]
+    fn error_len_three_for_break_after_two_good_continuation_bytes() {
+        use super::validate_utf8_strict;
+
+        // A legal four-byte lead and its first two continuation bytes,
+        // then a byte that isn't a continuation byte at all.
+        let err = validate_utf8_strict(b"\xF0\x90\x80\x28").unwrap_err();
+        assert_eq!(err.valid_up_to, 0);
+        assert_eq!(err.error_len, Some(3));
+    }
+
+    #[test]
+    fn error_len_none_for_truncated_trailing_sequences() {
+        use super::validate_utf8_strict;
+
+        for &buf in &[&b"\xE2\x82"[..], b"\xF0\x90\x80", b"\xC2"] {
+            let err = validate_utf8_strict(buf).unwrap_err();
+            assert_eq!(err.valid_up_to, 0);
+            assert_eq!(err.error_len, None);
+        }
+    }
+
+    #[test]
+    fn validate_utf8_strict_agrees_with_validate_utf8_on_curated_corpus() {
+        use super::{validate_utf8, validate_utf8_strict};
+
+        let corpus: &[&[u8]] = &[
+            b"hello world",
+            b"caf\xC3\xA9",
+            b"\xC0\x80",
+            b"\xE0\x80\x80",
+            b"\xED\xA0\x80",
+            b"\xF5\x80\x80\x80",
+            b"\xE2\x82",
+            b"",
+        ];
+
+        for buf in corpus {
+            assert_eq!(validate_utf8_strict(buf), validate_utf8(buf));
+        }
+    }
+
+    const _: () = assert!(super::validate_utf8_const(b"hello").is_ok());
+    const _: () = assert!(super::validate_utf8_const(b"caf\xC3\xA9").is_ok());
+    const _: () = assert!(super::validate_utf8_const(b"\xFF").is_err());
+    const _: () = assert!(super::validate_utf8_const(b"").is_ok());
+
+    #[test]
+    fn validate_utf8_const_agrees_with_validate_utf8_on_curated_corpus() {
+        use super::{validate_utf8, validate_utf8_const};
+
+        let corpus: &[&[u8]] = &[
+            b"hello world",
+            b"caf\xC3\xA9",
+            b"\xC0\x80",
+            b"\xE0\x80\x80",
+            b"\xED\xA0\x80",
+            b"\xF5\x80\x80\x80",
+            b"\xE2\x82",
+            b"",
+        ];
+
+        for buf in corpus {
+            assert_eq!(validate_utf8_const(buf), validate_utf8(buf));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "prefetch")]
+    fn prefetch_does_not_change_validation_results() {
+        use super::{validate_utf8, validate_utf8_with_stats};
+
+        let mut buf = vec![b'a'; 10 * 8 * super::WORD_BYTES];
+        buf.extend_from_slice("希望".as_bytes());
+        buf.push(0xFF);
+        buf.extend_from_slice(&[b'b'; 4096]);
+
+        assert_eq!(validate_utf8(&buf), validate_utf8_with_stats(&buf, None));
+        assert!(validate_utf8(&buf).is_err());
+
+        buf.truncate(buf.len() - 4097);
+        assert!(validate_utf8(&buf).is_ok());
+    }
+
+    #[test]
+    fn with_offset_applies_base_only_on_the_error_path() {
+        use super::validate_utf8_with_offset;
+
+        assert!(validate_utf8_with_offset(b"hello", 100).is_ok());
+
+        let err = validate_utf8_with_offset(b"ok\xFFmore", 100).unwrap_err();
+        assert_eq!(err.valid_up_to, 102);
+        assert_eq!(err.error_len, Some(1));
+    }
+
+    #[test]
+    fn skip_bom_reports_three_for_a_bom_prefixed_string() {
+        use super::validate_utf8_skip_bom;
+
+        assert_eq!(validate_utf8_skip_bom(b"\xEF\xBB\xBFhello"), Ok(3));
+    }
+
+    #[test]
+    fn skip_bom_reports_three_for_a_bare_bom() {
+        use super::validate_utf8_skip_bom;
+
+        assert_eq!(validate_utf8_skip_bom(b"\xEF\xBB\xBF"), Ok(3));
+    }
+
+    #[test]
+    fn skip_bom_reports_zero_when_absent() {
+        use super::validate_utf8_skip_bom;
+
+        assert_eq!(validate_utf8_skip_bom(b"hello"), Ok(0));
+    }
+
+    #[test]
+    fn skip_bom_does_not_skip_a_bom_appearing_mid_buffer() {
+        use super::validate_utf8_skip_bom;
+
+        let buf = [b"ab", &b"\xEF\xBB\xBF"[..], b"cd"].concat();
+        assert_eq!(validate_utf8_skip_bom(&buf), Ok(0));
+    }
+
+    #[cfg(feature = "reference-impl")]
+    proptest::proptest! {
+        #[test]
+        fn differential_agrees_with_std_on_arbitrary_bytes(buf: Vec<u8>) {
+            use super::validate_utf8_differential;
+
+            let (fast, std) = validate_utf8_differential(&buf);
+            proptest::prop_assert_eq!(fast, std);
+        }
+    }
+
+    /// A byte distribution weighted heavily toward ASCII with occasional
+    /// high bytes, for exercising the block-to-bytewise transitions that a
+    /// uniformly-random `Vec<u8>` mostly skips past (a uniform byte is
+    /// non-ASCII half the time, so the 8x/2x block loops rarely get to
+    /// run for more than an iteration or two).
+    fn mostly_ascii_byte() -> impl proptest::strategy::Strategy<Value = u8> {
+        proptest::prop_oneof![9 => 0u8..0x80, 1 => proptest::prelude::any::<u8>()]
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn validate_utf8_matches_std_str_from_utf8_on_arbitrary_bytes(buf: Vec<u8>) {
+            let ours = validate_utf8(&buf);
+            let std_result = core::str::from_utf8(&buf);
+
+            proptest::prop_assert_eq!(ours.is_ok(), std_result.is_ok());
+            if let (Err(e), Err(std_e)) = (&ours, &std_result) {
+                proptest::prop_assert_eq!(e.valid_up_to, std_e.valid_up_to());
+                proptest::prop_assert_eq!(e.error_len, std_e.error_len().map(|l| l as u8));
+            }
+        }
+
+        #[test]
+        fn validate_utf8_matches_std_str_from_utf8_on_mostly_ascii_bytes(
+            buf in proptest::collection::vec(mostly_ascii_byte(), 0..4096),
+        ) {
+            let ours = validate_utf8(&buf);
+            let std_result = core::str::from_utf8(&buf);
+
+            proptest::prop_assert_eq!(ours.is_ok(), std_result.is_ok());
+            if let (Err(e), Err(std_e)) = (&ours, &std_result) {
+                proptest::prop_assert_eq!(e.valid_up_to, std_e.valid_up_to());
+                proptest::prop_assert_eq!(e.error_len, std_e.error_len().map(|l| l as u8));
+            }
+        }
+    }
+
+    #[test]
+    fn count_invalid_sequences_matches_from_utf8_lossy_replacement_count() {
+        use super::count_invalid_sequences;
+
+        let inputs: &[&[u8]] = &[
+            b"hello world",
+            b"a\xFFb\xFEc",
+            b"hi \xE2\x82",
+            b"a\xFFb\xC0\x80c",
+            b"\xF4\x90\x80\x80",
+            b"",
+        ];
+
+        for buf in inputs {
+            let lossy = String::from_utf8_lossy(buf);
+            let expected = lossy.chars().filter(|&c| c == char::REPLACEMENT_CHARACTER).count();
+            assert_eq!(count_invalid_sequences(buf), expected, "buf = {buf:?}");
+        }
+    }
+
+    #[test]
+    fn visit_ascii_reconstructs_the_input_alongside_the_multi_byte_chars() {
+        use super::validate_utf8_visit_ascii;
+
+        let input = "ab中cd\u{1F600}ef".as_bytes();
+        let mut reconstructed = Vec::new();
+        let mut pos = 0;
+
+        validate_utf8_visit_ascii(input, |run| {
+            reconstructed.extend_from_slice(run);
+            pos += run.len();
+
+            // whatever wasn't just an ASCII run is a multi-byte character
+            // this callback didn't see directly; borrow it straight from
+            // the input to reconstruct the buffer exactly
+            while pos < input.len() && input[pos] >= 128 {
+                reconstructed.push(input[pos]);
+                pos += 1;
+            }
+        })
+        .unwrap();
+
+        assert_eq!(reconstructed, input);
+    }
+
+    #[test]
+    fn visit_ascii_propagates_the_validation_error() {
+        use super::validate_utf8_visit_ascii;
+
+        let err = validate_utf8_visit_ascii(b"ok\xFF", |_run| {}).unwrap_err();
+        assert_eq!(err.valid_up_to, 2);
+        assert_eq!(err.error_len, Some(1));
+    }
+
+    /// Enumerates known overlong encodings and the full surrogate range,
+    /// asserting each is rejected with the same `valid_up_to`/`error_len`
+    /// std reports — a dedicated safety net for the range checks in
+    /// [`validate_non_acii_bytes`], since it's not obvious from reading
+    /// the code alone that they reject these without a test spelling it
+    /// out.
+    mod overlong_and_surrogate_rejection {
+        use super::super::validate_utf8;
+
+        fn assert_rejected_like_std(buf: &[u8]) {
+            let ours = validate_utf8(buf).unwrap_err();
+            let std_err = core::str::from_utf8(buf).unwrap_err();
+            assert_eq!(ours.valid_up_to, std_err.valid_up_to(), "buf = {buf:02X?}");
+            assert_eq!(ours.error_len, std_err.error_len().map(|l| l as u8), "buf = {buf:02X?}");
+        }
+
+        #[test]
+        fn two_byte_overlong_encodings() {
+            // 0xC0/0xC1 can only ever encode code points below U+0080, so
+            // both lead bytes are unconditionally invalid.
+            for lead in 0xC0..=0xC1u8 {
+                for cont in 0x80..=0xBFu8 {
+                    assert_rejected_like_std(&[lead, cont]);
+                }
+            }
+        }
+
+        #[test]
+        fn three_byte_overlong_encodings() {
+            // `E0 80 80` through `E0 9F BF` re-encode code points already
+            // representable in two bytes or fewer.
+            for cont1 in 0x80..=0x9Fu8 {
+                assert_rejected_like_std(&[0xE0, cont1, 0x80]);
+            }
+        }
+
+        #[test]
+        fn four_byte_overlong_encodings() {
+            // `F0 80 80 80` through `F0 8F BF BF` re-encode code points
+            // already representable in three bytes or fewer.
+            for cont1 in 0x80..=0x8Fu8 {
+                assert_rejected_like_std(&[0xF0, cont1, 0x80, 0x80]);
+            }
+        }
+
+        #[test]
+        fn surrogate_range_ed_a0_80_through_ed_bf_bf() {
+            for cont1 in 0xA0..=0xBFu8 {
+                for cont2 in [0x80u8, 0xBF] {
+                    assert_rejected_like_std(&[0xED, cont1, cont2]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn validate_many_reports_one_result_per_item() {
+        use super::validate_many;
+
+        let items: &[&[u8]] = &[b"ok", b"\xFF", b"also ok", b"\xC0\x80"];
+        let results = validate_many(items.iter().copied());
+
+        assert_eq!(results.len(), 4);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(results[2].is_ok());
+        assert!(results[3].is_err());
+    }
+
+    #[test]
+    fn validate_all_reports_the_first_failing_index() {
+        use super::validate_all;
+
+        let items: &[&[u8]] = &[b"ok", b"still ok", b"\xFF", b"never reached"];
+        let (index, err) = validate_all(items.iter().copied()).unwrap_err();
+
+        assert_eq!(index, 2);
+        assert_eq!(err.valid_up_to, 0);
+    }
+
+    #[test]
+    fn validate_all_succeeds_when_every_item_is_valid() {
+        use super::validate_all;
+
+        let items: &[&[u8]] = &[b"ok", b"still ok", b"caf\xC3\xA9"];
+        assert!(validate_all(items.iter().copied()).is_ok());
+    }
+
+    #[test]
+    fn dispatch_caches_a_stable_backend_pointer_across_calls() {
+        use super::{dispatch, force_scalar_backend};
+
+        // `dispatch`'s cache is a process-wide `AtomicUsize`, so (like
+        // `force_scalar_backend_is_observed_after_being_set` above) forcing
+        // the scalar backend here only decides what gets cached if no
+        // other test already resolved and cached one first. What's true
+        // regardless of test execution order is that once resolved, the
+        // pointer never changes: repeated calls return the exact same
+        // `fn`.
+        force_scalar_backend();
+
+        let first = dispatch();
+        let second = dispatch();
+        assert_eq!(first as usize, second as usize);
+    }
+
+    #[test]
+    fn choose_backend_picks_scalar_below_threshold_regardless_of_availability() {
+        use super::{choose_backend, Backend, AVX2_THRESHOLD};
+
+        assert_eq!(choose_backend(AVX2_THRESHOLD - 1, true), Backend::Scalar);
+        assert_eq!(choose_backend(0, true), Backend::Scalar);
+    }
+
+    #[test]
+    fn choose_backend_picks_avx2_at_threshold_only_when_available() {
+        use super::{choose_backend, Backend, AVX2_THRESHOLD};
+
+        assert_eq!(choose_backend(AVX2_THRESHOLD, true), Backend::Avx2);
+        assert_eq!(choose_backend(AVX2_THRESHOLD, false), Backend::Scalar);
+    }
+
+    #[test]
+    fn bounded_backs_up_to_the_boundary_when_the_budget_lands_inside_a_three_byte_sequence() {
+        use super::validate_utf8_bounded;
+
+        // "€" is `\xE2\x82\xAC`, a 3-byte sequence starting at index 2
+        let buf = "ab\u{20AC}cd".as_bytes();
+
+        // land the budget on the 2nd byte of the 3-byte sequence
+        assert_eq!(validate_utf8_bounded(buf, 3).unwrap(), 2);
+        // landing exactly on its first byte should also back up to 2
+        assert_eq!(validate_utf8_bounded(buf, 2).unwrap(), 2);
+        // once the whole character is included, the boundary moves past it
+        assert_eq!(validate_utf8_bounded(buf, 5).unwrap(), 5);
+        // a budget past the buffer's length is simply clamped
+        assert_eq!(validate_utf8_bounded(buf, 100).unwrap(), buf.len());
+    }
+
+    #[test]
+    fn bounded_still_reports_a_real_error_within_the_budget() {
+        use super::validate_utf8_bounded;
+
+        let buf = b"ok\xFFmore";
+        let err = validate_utf8_bounded(buf, buf.len()).unwrap_err();
+        assert_eq!(err.valid_up_to(), 2);
+    }
+
+    #[test]
+    fn with_stats_still_validates_zero_length_and_tiny_buffers_after_hoisting_the_align_check() {
+        use super::validate_utf8_with_stats;
+
+        assert!(validate_utf8_with_stats(b"", None).is_ok());
+        assert!(validate_utf8_with_stats(b"a", None).is_ok());
+        assert!(validate_utf8_with_stats(b"ab", None).is_ok());
+        assert!(validate_utf8_with_stats(b"\xC3\xA9", None).is_ok());
+        assert_eq!(
+            validate_utf8_with_stats(b"\xFF", None).unwrap_err().valid_up_to(),
+            0
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn atomic_stats_snapshot_matches_the_serial_merge_of_the_same_chunks() {
+        use super::{validate_utf8_with_atomic_stats, validate_utf8_with_stats, AtomicStatistics, Statistics};
+
+        const ENGLISH: &str = include_str!("../assets/english_971kb.txt");
+
+        // split on char boundaries near every 4096 bytes so no chunk cuts
+        // a multi-byte sequence in half
+        let mut chunks = Vec::new();
+        let mut rest = ENGLISH;
+        while !rest.is_empty() {
+            let mut split = rest.len().min(4096);
+            while !rest.is_char_boundary(split) {
+                split -= 1;
+            }
+            let (chunk, remainder) = rest.split_at(split);
+            chunks.push(chunk.as_bytes());
+            rest = remainder;
+        }
+
+        let mut serial = Statistics::default();
+        for chunk in &chunks {
+            validate_utf8_with_stats(chunk, Some(&mut serial)).unwrap();
+        }
+
+        let atomic = AtomicStatistics::default();
+        std::thread::scope(|scope| {
+            for chunk in &chunks {
+                let atomic = &atomic;
+                scope.spawn(move || {
+                    validate_utf8_with_atomic_stats(chunk, atomic).unwrap();
+                });
+            }
+        });
+
+        assert_eq!(atomic.snapshot(), serial);
+    }
+
+    #[test]
+    fn active_backend_reports_one_of_the_known_names() {
+        use super::active_backend;
+
+        let name = active_backend();
+        assert!(
+            matches!(name, "avx2" | "neon" | "simd128" | "scalar"),
+            "unexpected backend name: {name}"
+        );
+
+        // this target/feature combination never compiles in an ISA
+        // backend, so the only backend that can ever be selected is the
+        // portable scalar one
+        #[cfg(not(all(
+            feature = "simd",
+            any(
+                all(feature = "std", target_arch = "x86_64"),
+                all(feature = "std", target_arch = "aarch64"),
+                all(target_arch = "wasm32", target_feature = "simd128"),
+            )
+        )))]
+        assert_eq!(name, "scalar");
+    }
+
+    #[test]
+    fn utf8_error_from_std_matches_this_crates_own_validator_on_the_same_input() {
+        use super::{validate_utf8, Utf8Error};
+
+        let buf: Vec<u8> = b"caf".iter().chain(b"\xC3\x28").copied().collect();
+        let std_err = core::str::from_utf8(&buf).unwrap_err();
+        let converted: Utf8Error = std_err.into();
+        let ours = validate_utf8(&buf).unwrap_err();
+
+        assert_eq!(converted.valid_up_to(), ours.valid_up_to());
+        assert_eq!(converted.error_len(), ours.error_len());
+    }
+
+    #[test]
+    fn utf8_error_new_round_trips_through_its_accessors() {
+        use super::Utf8Error;
+
+        let err = Utf8Error::new(3, Some(1));
+        assert_eq!(err.valid_up_to(), 3);
+        assert_eq!(err.error_len(), Some(1));
+        assert_eq!(err.error_byte(), None);
+        assert!(err.is_invalid());
+        assert!(!err.is_incomplete());
+
+        let incomplete = Utf8Error::new(5, None);
+        assert_eq!(incomplete.valid_up_to(), 5);
+        assert_eq!(incomplete.error_len(), None);
+        assert!(incomplete.is_incomplete());
+    }
+
+    #[test]
+    fn valid_chunks_skips_invalid_regions_and_yields_the_valid_runs_between_them() {
+        use super::ValidChunks;
+
+        let buf: &[u8] = b"ok\xFFmore\xC3\x28end";
+        let chunks: Vec<&str> = ValidChunks::new(buf).collect();
+
+        assert_eq!(chunks, vec!["ok", "more", "(end"]);
+    }
+
+    #[test]
+    fn valid_chunks_yields_the_whole_buffer_as_one_run_when_fully_valid() {
+        use super::ValidChunks;
+
+        let chunks: Vec<&str> = ValidChunks::new("hello world".as_bytes()).collect();
+        assert_eq!(chunks, vec!["hello world"]);
+    }
+
+    #[test]
+    fn from_utf8_prefix_splits_valid_prefix_from_remainder_on_a_mid_buffer_error() {
+        use super::from_utf8_prefix;
+
+        let buf = b"hello\xFFworld";
+        let (prefix, remainder) = from_utf8_prefix(buf);
+
+        assert_eq!(prefix, "hello");
+        assert_eq!(remainder, b"\xFFworld");
+
+        let mut reassembled = prefix.as_bytes().to_vec();
+        reassembled.extend_from_slice(remainder);
+        assert_eq!(reassembled, buf);
+    }
+
+    #[test]
+    fn from_utf8_prefix_returns_the_whole_buffer_and_an_empty_remainder_when_valid() {
+        use super::from_utf8_prefix;
+
+        let (prefix, remainder) = from_utf8_prefix("caf\u{e9}".as_bytes());
+
+        assert_eq!(prefix, "caf\u{e9}");
+        assert!(remainder.is_empty());
+    }
+
+    #[test]
+    fn ascii_biased_agrees_with_default_validator_on_bundled_assets() {
+        use super::validate_utf8_ascii_biased;
+
+        const ENGLISH: &str = include_str!("../assets/english_971kb.txt");
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+
+        assert!(validate_utf8_ascii_biased(ENGLISH.as_bytes()).is_ok());
+        assert!(validate_utf8_ascii_biased(CHINESE.as_bytes()).is_ok());
+        assert_eq!(
+            validate_utf8_ascii_biased(b"ab\xFFcd"),
+            super::validate_utf8(b"ab\xFFcd"),
+        );
+    }
+
+    #[test]
+    fn unicode_biased_agrees_with_default_validator_on_bundled_assets() {
+        use super::validate_utf8_unicode_biased;
+
+        const ENGLISH: &str = include_str!("../assets/english_971kb.txt");
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+
+        assert!(validate_utf8_unicode_biased(ENGLISH.as_bytes()).is_ok());
+        assert!(validate_utf8_unicode_biased(CHINESE.as_bytes()).is_ok());
+        assert_eq!(
+            validate_utf8_unicode_biased(b"ab\xFFcd"),
+            super::validate_utf8(b"ab\xFFcd"),
+        );
+    }
+
+    #[test]
+    fn auto_agrees_with_default_validator_on_bundled_assets() {
+        use super::validate_utf8_auto;
+
+        const ENGLISH: &str = include_str!("../assets/english_971kb.txt");
+        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+
+        assert!(validate_utf8_auto(ENGLISH.as_bytes()).is_ok());
+        assert!(validate_utf8_auto(CHINESE.as_bytes()).is_ok());
+        assert_eq!(
+            validate_utf8_auto(b"ab\xFFcd"),
+            super::validate_utf8(b"ab\xFFcd"),
+        );
+    }
+
+    #[test]
+    fn force_scalar_backend_is_observed_after_being_set() {
+        use super::{force_scalar_backend, scalar_backend_forced};
+
+        // Global, process-wide flag: only assert monotonicity, since other
+        // tests running concurrently may have already set it.
+        force_scalar_backend();
+        assert!(scalar_backend_forced());
+    }
+
+    #[test]
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    fn avx2_agrees_with_portable_scan_at_every_lane_alignment() {
+        use super::{validate_utf8, validate_utf8_with_stats};
+
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        // a non-ASCII byte at every offset within one 32-byte AVX2 lane,
+        // padded with ASCII on both sides so the lane loop and the
+        // bytewise tail both get exercised
+        for offset in 0..32 {
+            let mut buf = vec![b'a'; 64];
+            buf[offset] = 0xFF;
+
+            let simd_result = validate_utf8(&buf);
+            let portable_result = validate_utf8_with_stats(&buf, None);
+            assert_eq!(simd_result, portable_result, "mismatch at offset {offset}");
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    fn avx2_agrees_with_portable_scan_on_bundled_assets() {
+        use super::{validate_utf8, validate_utf8_with_stats};
+
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let assets: &[&[u8]] = &[
+            GERMAN_UTF8_16KB.as_bytes(),
+            include_bytes!("../assets/chinese_1mb.txt"),
+        ];
+
+        for asset in assets {
+            assert_eq!(validate_utf8(asset), validate_utf8_with_stats(asset, None));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    fn avx2_movemask_position_matches_scalar_non_ascii_byte_position_at_every_offset() {
+        use super::{mask_block, non_ascii_byte_position, simd_x86, WORD_BYTES};
+
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        const LANE: usize = 32;
+        const WORDS: usize = LANE / WORD_BYTES;
+
+        for offset in 0..LANE {
+            let mut buf = [0u8; LANE];
+            buf[offset] = 0xFF;
+
+            // SAFETY: `buf` is exactly `LANE` bytes and AVX2 was just
+            // confirmed available
+            let simd_offset = unsafe { simd_x86::first_non_ascii(buf.as_ptr()) }.unwrap();
+
+            let words: [usize; WORDS] =
+                unsafe { core::ptr::read_unaligned(buf.as_ptr().cast::<[usize; WORDS]>()) };
+            let masked = mask_block(&words);
+            // SAFETY: `buf[offset]` is non-ASCII, so `masked` has a
+            // non-zero word somewhere
+            let scalar_offset = unsafe { non_ascii_byte_position(&masked) };
+
+            assert_eq!(simd_offset, scalar_offset, "mismatch at offset {offset}");
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "simd", target_arch = "aarch64"))]
+    fn neon_agrees_with_portable_scan_at_every_lane_alignment() {
+        use super::{validate_utf8, validate_utf8_with_stats};
+
+        if !std::arch::is_aarch64_feature_detected!("neon") {
+            return;
+        }
+
+        // a non-ASCII byte at every offset within one 16-byte NEON lane,
+        // padded with ASCII on both sides so the lane loop and the
+        // bytewise tail both get exercised
+        for offset in 0..16 {
+            let mut buf = vec![b'a'; 32];
+            buf[offset] = 0xFF;
+
+            let simd_result = validate_utf8(&buf);
+            let portable_result = validate_utf8_with_stats(&buf, None);
+            assert_eq!(simd_result, portable_result, "mismatch at offset {offset}");
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "simd", target_arch = "aarch64"))]
+    fn neon_agrees_with_portable_scan_on_bundled_assets() {
+        use super::{validate_utf8, validate_utf8_with_stats};
+
+        if !std::arch::is_aarch64_feature_detected!("neon") {
+            return;
+        }
+
+        let assets: &[&[u8]] = &[
+            GERMAN_UTF8_16KB.as_bytes(),
+            include_bytes!("../assets/japanese_203kb.txt"),
+        ];
+
+        for asset in assets {
+            assert_eq!(validate_utf8(asset), validate_utf8_with_stats(asset, None));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))]
+    fn simd128_agrees_with_portable_scan_at_every_lane_alignment() {
+        use super::{validate_utf8, validate_utf8_with_stats};
+
+        // a non-ASCII byte at every offset within one 16-byte simd128 lane,
+        // padded with ASCII on both sides so the lane loop and the
+        // bytewise tail both get exercised
+        for offset in 0..16 {
+            let mut buf = vec![b'a'; 32];
+            buf[offset] = 0xFF;
+
+            let simd_result = validate_utf8(&buf);
+            let portable_result = validate_utf8_with_stats(&buf, None);
+            assert_eq!(simd_result, portable_result, "mismatch at offset {offset}");
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))]
+    fn simd128_agrees_with_portable_scan_on_bundled_assets() {
+        use super::{validate_utf8, validate_utf8_with_stats};
+
+        let assets: &[&[u8]] = &[
+            GERMAN_UTF8_16KB.as_bytes(),
+            include_bytes!("../assets/chinese_1mb.txt"),
+        ];
+
+        for asset in assets {
+            assert_eq!(validate_utf8(asset), validate_utf8_with_stats(asset, None));
+        }
+    }
+
+    #[test]
+    fn ascii_density_is_one_for_plain_ascii() {
+        let (density, result) = super::validate_utf8_returning_ascii_density(b"hello world");
+        assert_eq!(result, Ok(()));
+        assert_eq!(density, 1.0);
+    }
+
+    #[test]
+    fn ascii_density_matches_manual_count_for_mixed_text() {
+        let buf = "café".as_bytes(); // 3 ASCII bytes, 2 non-ASCII bytes
+        let (density, result) = super::validate_utf8_returning_ascii_density(buf);
+        assert_eq!(result, Ok(()));
+        assert_eq!(density, 3.0 / 5.0);
+    }
+
+    #[test]
+    fn ascii_density_is_zero_for_empty_buffer() {
+        let (density, result) = super::validate_utf8_returning_ascii_density(b"");
+        assert_eq!(result, Ok(()));
+        assert_eq!(density, 0.0);
+    }
+
+    #[test]
+    fn ascii_density_still_reports_the_error() {
+        let (_density, result) = super::validate_utf8_returning_ascii_density(b"ab\xFF");
+        assert_eq!(result, super::validate_utf8(b"ab\xFF"));
+    }
+
+    #[test]
+    fn ascii_density_matches_manual_count_across_a_word_boundary() {
+        // Exercises the word-at-a-time loop plus its scalar tail.
+        let buf = [b'a'; 23];
+        let (density, _) = super::validate_utf8_returning_ascii_density(&buf);
+        assert_eq!(density, 1.0);
+    }
+
+    #[test]
+    fn stream_large_agrees_with_validate_utf8_below_threshold() {
+        assert_eq!(
+            super::validate_utf8_stream_large(b"hello world"),
+            super::validate_utf8(b"hello world")
+        );
+    }
+
+    #[test]
+    fn stream_large_agrees_with_validate_utf8_above_threshold() {
+        let mut buf = vec![b'a'; 200_000];
+        buf[150_000] = 0xFF;
+        assert_eq!(
+            super::validate_utf8_stream_large(&buf),
+            super::validate_utf8(&buf)
+        );
+    }
+
+    #[test]
+    fn error_histogram_counts_a_lone_stray_continuation() {
+        use super::{error_kind_histogram, Utf8ErrorKind};
+
+        let (histogram, total_invalid_bytes) = error_kind_histogram(b"a\x80b");
+        assert_eq!(histogram.get(&Utf8ErrorKind::StrayContinuation), Some(&1));
+        assert_eq!(total_invalid_bytes, 1);
+    }
+
+    #[test]
+    fn error_histogram_counts_an_overlong_lead_byte() {
+        use super::{error_kind_histogram, Utf8ErrorKind};
+
+        // 0xE0 immediately followed by 0x80 is only classified once, on
+        // the lead byte itself; the two now-orphaned continuation bytes
+        // resync as their own stray-continuation errors.
+        let (histogram, _) = error_kind_histogram(&[0xE0, 0x80, 0x80]);
+        assert_eq!(histogram.get(&Utf8ErrorKind::Overlong), Some(&1));
+    }
+
+    #[test]
+    fn error_histogram_counts_a_surrogate_lead_byte() {
+        use super::{error_kind_histogram, Utf8ErrorKind};
+
+        let (histogram, _) = error_kind_histogram(&[0xED, 0xA0, 0x80]);
+        assert_eq!(histogram.get(&Utf8ErrorKind::Surrogate), Some(&1));
+    }
+
+    #[test]
+    fn error_histogram_counts_an_out_of_range_scalar() {
+        use super::{error_kind_histogram, Utf8ErrorKind};
+
+        let (histogram, _) = error_kind_histogram(&[0xF4, 0x90, 0x80, 0x80]);
+        assert_eq!(histogram.get(&Utf8ErrorKind::OutOfRange), Some(&1));
+    }
+
+    #[test]
+    fn error_histogram_counts_an_invalid_lead_byte() {
+        use super::{error_kind_histogram, Utf8ErrorKind};
+
+        let (histogram, total_invalid_bytes) = error_kind_histogram(b"a\xC1b");
+        assert_eq!(histogram.get(&Utf8ErrorKind::InvalidLead), Some(&1));
+        assert_eq!(total_invalid_bytes, 1);
+    }
+
+    #[test]
+    fn error_histogram_counts_a_truncated_tail() {
+        use super::{error_kind_histogram, Utf8ErrorKind};
+
+        let (histogram, total_invalid_bytes) = error_kind_histogram(b"hi \xE2\x82");
+        assert_eq!(histogram.get(&Utf8ErrorKind::Truncated), Some(&1));
+        assert_eq!(total_invalid_bytes, 2);
+    }
+
+    #[test]
+    fn error_histogram_is_empty_for_valid_utf8() {
+        use super::error_kind_histogram;
+
+        let (histogram, total_invalid_bytes) = error_kind_histogram(b"clean ascii text");
+        assert!(histogram.is_empty());
+        assert_eq!(total_invalid_bytes, 0);
+    }
+
+    #[test]
+    fn nth_error_finds_first_and_later_errors_at_absolute_offsets() {
+        use super::nth_error;
+
+        // three lone stray continuation bytes, five bytes apart
+        let buf = b"ab\x80cd\x80ef\x80gh";
+
+        assert_eq!(
+            nth_error(buf, 0),
+            Some(super::Utf8Error { valid_up_to: 2, error_len: Some(1), error_byte: Some(0x80) })
+        );
+        assert_eq!(
+            nth_error(buf, 1),
+            Some(super::Utf8Error { valid_up_to: 5, error_len: Some(1), error_byte: Some(0x80) })
+        );
+        assert_eq!(
+            nth_error(buf, 2),
+            Some(super::Utf8Error { valid_up_to: 8, error_len: Some(1), error_byte: Some(0x80) })
+        );
+    }
+
+    #[test]
+    fn nth_error_is_none_past_the_last_error() {
+        use super::nth_error;
+
+        let buf = b"ab\x80cd";
+        assert_eq!(nth_error(buf, 1), None);
+    }
+
+    #[test]
+    fn nth_error_is_none_for_valid_utf8() {
+        use super::nth_error;
+
+        assert_eq!(nth_error(b"clean ascii text", 0), None);
+    }
+
+    #[test]
+    fn nth_error_reports_truncated_tail_as_final_error() {
+        use super::nth_error;
+
+        // a lone stray continuation, then a truncated 3-byte lead at the end
+        let buf = [b'a', 0x80, b'b', 0xE2, 0x82];
+        assert_eq!(
+            nth_error(&buf, 1),
+            Some(super::Utf8Error { valid_up_to: 3, error_len: None, error_byte: None })
+        );
+    }
+
+    #[test]
+    fn find_first_of_locates_earliest_needle() {
+        let buf = "café,rows;done".as_bytes();
+        assert_eq!(
+            super::validate_utf8_with_simd_find_first_of(buf, b",;"),
+            Ok(Some(buf.iter().position(|&b| b == b',').unwrap()))
+        );
+    }
+
+    #[test]
+    fn find_first_of_none_when_absent() {
+        assert_eq!(
+            super::validate_utf8_with_simd_find_first_of("café".as_bytes(), b",;"),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn find_first_of_across_word_boundary() {
+        let mut buf = vec![b'a'; super::WORD_BYTES + 3];
+        buf.push(b'\t');
+        assert_eq!(
+            super::validate_utf8_with_simd_find_first_of(&buf, b"\t"),
+            Ok(Some(super::WORD_BYTES + 3))
+        );
+    }
+
+    #[test]
+    fn find_first_of_reports_error_before_any_match() {
+        let err =
+            super::validate_utf8_with_simd_find_first_of(b"ab\xFF,cd", b",").unwrap_err();
+        assert_eq!(err, super::validate_utf8(b"ab\xFF,cd").unwrap_err());
+    }
+
+    #[test]
+    fn batch_short_validates_all_lines() {
+        let lines: Vec<&[u8]> = vec![b"hello", "café".as_bytes(), b"world"];
+        assert_eq!(super::validate_utf8_batch_short(&lines), Ok(()));
+    }
+
+    #[test]
+    fn batch_short_reports_index_of_bad_line() {
+        let lines: Vec<&[u8]> = vec![b"hello", b"\xFF\xFF", b"world"];
+        let (idx, err) = super::validate_utf8_batch_short(&lines).unwrap_err();
+        assert_eq!(idx, 1);
+        assert_eq!(err, super::validate_utf8(b"\xFF\xFF").unwrap_err());
+    }
+
+    #[test]
+    fn chunked_validation_handles_seam_straddling_char() {
+        // a 3-byte char placed so a naive split would land mid-sequence
+        let mut buf = vec![b'a'; 15];
+        buf.extend_from_slice("€".as_bytes());
+        buf.extend(vec![b'b'; 15]);
+
+        assert_eq!(
+            super::validate_utf8_parallel_chunked_with_correct_offsets(&buf, 4),
+            Ok(())
+        );
+    }
+
+    // Boundary corpus for the maximum scalar value, U+10FFFF. Locks down
+    // that `validate_non_acii_bytes` rejects everything past it (rather
+    // than relying on that falling out of the match arms by accident).
+    #[test]
+    fn max_scalar_accepts_u10ffff() {
+        // F4 8F BF BF encodes exactly U+10FFFF, the highest valid scalar.
+        assert_eq!(validate_utf8(&[0xF4, 0x8F, 0xBF, 0xBF]), Ok(()));
+    }
+
+    #[test]
+    fn max_scalar_rejects_one_past_u10ffff() {
+        // F4 90 80 80 would encode U+110000, one past the maximum scalar.
+        let err = validate_utf8(&[0xF4, 0x90, 0x80, 0x80]).unwrap_err();
+        assert_eq!(
+            err,
+            super::Utf8Error { valid_up_to: 0, error_len: Some(1), error_byte: Some(0x90) }
         );
+    }
+
+    #[test]
+    fn max_scalar_rejects_lead_bytes_above_f4() {
+        for lead in 0xF5u8..=0xFF {
+            let err = validate_utf8(&[lead, 0x80, 0x80, 0x80]).unwrap_err();
+            assert_eq!(
+                err,
+                super::Utf8Error { valid_up_to: 0, error_len: Some(1), error_byte: Some(lead) },
+                "lead byte {lead:#04X} should be rejected",
+            );
+        }
+    }
+
+    #[test]
+    fn max_scalar_accepts_smallest_four_byte_sequence() {
+        // F0 90 80 80 encodes U+10000, the smallest scalar needing 4 bytes.
+        assert_eq!(validate_utf8(&[0xF0, 0x90, 0x80, 0x80]), Ok(()));
+    }
+
+    #[test]
+    fn baseline_block_widths_agree_with_std_on_bundled_assets() {
+        use super::validate_utf8_baseline;
+
+        let assets: &[&[u8]] = &[
+            GERMAN_UTF8_16KB.as_bytes(),
+            include_bytes!("../assets/chinese_1mb.txt"),
+            include_bytes!("../assets/greek_152kb.txt"),
+            include_bytes!("../assets/arabic_21kb.txt"),
+        ];
+
+        for asset in assets {
+            let expected = std::str::from_utf8(asset).is_ok();
+            assert_eq!(validate_utf8_baseline::<1>(asset).is_ok(), expected);
+            assert_eq!(validate_utf8_baseline::<4>(asset).is_ok(), expected);
+            assert_eq!(validate_utf8_baseline::<16>(asset).is_ok(), expected);
+        }
+    }
+
+    #[test]
+    fn baseline_reports_same_error_as_default_on_uneven_tail() {
+        use super::validate_utf8_baseline;
+
+        // 5 valid ASCII bytes (not a multiple of any of the block widths
+        // below) followed by a stray continuation byte
+        let buf = b"abcde\x80fgh";
+
+        assert_eq!(validate_utf8_baseline::<1>(buf), validate_utf8(buf));
+        assert_eq!(validate_utf8_baseline::<4>(buf), validate_utf8(buf));
+        assert_eq!(validate_utf8_baseline::<16>(buf), validate_utf8(buf));
+    }
+
+    #[test]
+    fn dynamic_agrees_with_default_on_bundled_assets() {
+        use super::validate_utf8_dynamic;
+
+        let assets: &[&[u8]] = &[
+            GERMAN_UTF8_16KB.as_bytes(),
+            include_bytes!("../assets/chinese_1mb.txt"),
+            include_bytes!("../assets/english_971kb.txt"),
+        ];
+
+        for asset in assets {
+            assert_eq!(validate_utf8_dynamic(asset), validate_utf8(asset));
+        }
+    }
+
+    #[test]
+    fn dynamic_reports_same_error_as_default() {
+        use super::validate_utf8_dynamic;
 
         assert_eq!(
-            validate_utf8(b"A\xC3\xA9 \xF1\x80 "),
+            validate_utf8_dynamic(b"A\xC3\xA9 \xF1 "),
             Err(super::Utf8Error {
                 valid_up_to: 4,
-                error_len: Some(2)
+                error_len: Some(1),
+                error_byte: Some(b' '),
             })
         );
     }
 
     #[test]
-    fn validate_mostly_ascii() {
-        assert!(validate_utf8(GERMAN_UTF8_16KB.as_bytes()).is_ok());
+    fn dynamic_escalates_back_to_8x_on_mostly_ascii_text() {
+        use super::validate_utf8_dynamic_with_stats;
+
+        const ENGLISH: &[u8] = include_bytes!("../assets/english_971kb.txt");
+
+        let mut stats = super::Statistics::default();
+        assert_eq!(validate_utf8_dynamic_with_stats(ENGLISH, Some(&mut stats)), Ok(()));
+        assert!(stats.optimistic_2x_to_8x > 0);
+        assert!(stats.success_blocks_8x > 0);
     }
 
     #[test]
-    fn invalid_ascii() {
-        let mut vec = Vec::from(GERMAN_UTF8_16KB);
-        vec.push(0xFF);
+    fn dynamic_stays_mostly_at_2x_on_low_ascii_text() {
+        use super::validate_utf8_dynamic_with_stats;
 
-        assert_eq!(validate_utf8(&vec).is_ok(), false);
+        const CHINESE: &[u8] = include_bytes!("../assets/chinese_1mb.txt");
+        const ENGLISH: &[u8] = include_bytes!("../assets/english_971kb.txt");
+
+        let mut chinese_stats = super::Statistics::default();
+        assert_eq!(
+            validate_utf8_dynamic_with_stats(CHINESE, Some(&mut chinese_stats)),
+            Ok(())
+        );
+
+        let mut english_stats = super::Statistics::default();
+        assert_eq!(
+            validate_utf8_dynamic_with_stats(ENGLISH, Some(&mut english_stats)),
+            Ok(())
+        );
+
+        // low-ASCII-density text should promote back to 8-word blocks far
+        // less often than mostly-ASCII text does
+        assert!(chinese_stats.optimistic_2x_to_8x < english_stats.optimistic_2x_to_8x);
     }
 
     #[test]
-    fn validate_utf() {
-        assert!(validate_utf8(b"Lorem ipsum dolor sit amet.").is_ok());
-        assert!(validate_utf8("Lörem ipsüm dölör sit ämet.".as_bytes()).is_ok());
+    fn up_to_returns_full_length_for_valid_utf8() {
+        use super::validate_utf8_up_to;
+
+        let text = "hello, café, 日本語";
+        assert_eq!(validate_utf8_up_to(text.as_bytes()), text.len());
     }
 
     #[test]
-    fn non_ascii_byte_count() {
-        unsafe {
-            let block = [0x7F7F7F7F_7F7F7FFF];
-            let res = super::non_ascii_byte_position(&block);
-            assert_eq!(res, 0);
-            let block = [0x7F7F7F7F_7F7FFF7F];
-            let res = super::non_ascii_byte_position(&block);
-            assert_eq!(res, 1);
-            let block = [0x7F7F7F7F_7FFF7F7F];
-            let res = super::non_ascii_byte_position(&block);
-            assert_eq!(res, 2);
-            let block = [0x7F7F7F7F_FF7F7F7F];
-            let res = super::non_ascii_byte_position(&block);
-            assert_eq!(res, 3);
-            let block = [0x7F7F7FFF_7F7F7F7F];
-            let res = super::non_ascii_byte_position(&block);
-            assert_eq!(res, 4);
-            let block = [0x7F7FFF7F_7F7F7F7F];
-            let res = super::non_ascii_byte_position(&block);
-            assert_eq!(res, 5);
-            let block = [0x7FFF7F7F_7F7F7F7F];
-            let res = super::non_ascii_byte_position(&block);
-            assert_eq!(res, 6);
-            let block = [0xFF7F7F7F_7F7F7F7F];
-            let res = super::non_ascii_byte_position(&block);
-            assert_eq!(res, 7);
-            let block = [0x7F7F7F7F_7F7F7F7F, 0x7F7F7F7F_7F7F7FFF];
-            let res = super::non_ascii_byte_position(&block);
-            assert_eq!(res, 8);
-            let block = [0x7F7F7F7F_7F7F7F7F, 0x7F7F7F7F_7F7FFF7F];
-            let res = super::non_ascii_byte_position(&block);
-            assert_eq!(res, 9);
+    fn up_to_stops_before_truncated_two_byte_sequence() {
+        use super::validate_utf8_up_to;
+
+        // "é" is 0xC3 0xA9; drop the continuation byte
+        let mut buf = b"abc".to_vec();
+        buf.push(0xC3);
+        assert_eq!(validate_utf8_up_to(&buf), 3);
+    }
+
+    #[test]
+    fn up_to_stops_before_truncated_three_byte_sequence() {
+        use super::validate_utf8_up_to;
+
+        // "€" is 0xE2 0x82 0xAC; keep only the first two bytes
+        let mut buf = b"abc".to_vec();
+        buf.extend_from_slice(&[0xE2, 0x82]);
+        assert_eq!(validate_utf8_up_to(&buf), 3);
+    }
+
+    #[test]
+    fn up_to_stops_before_truncated_four_byte_sequence() {
+        use super::validate_utf8_up_to;
+
+        // "😀" is 0xF0 0x9F 0x98 0x80; keep only the first three bytes
+        let mut buf = b"abc".to_vec();
+        buf.extend_from_slice(&[0xF0, 0x9F, 0x98]);
+        assert_eq!(validate_utf8_up_to(&buf), 3);
+    }
+
+    #[test]
+    fn up_to_stops_at_a_structural_error_too() {
+        use super::validate_utf8_up_to;
+
+        assert_eq!(validate_utf8_up_to(b"ab\x80cd"), 2);
+    }
+
+    #[test]
+    fn up_to_agrees_with_validate_utf8_on_bundled_assets() {
+        use super::validate_utf8_up_to;
+
+        let assets: &[&[u8]] = &[
+            GERMAN_UTF8_16KB.as_bytes(),
+            include_bytes!("../assets/chinese_1mb.txt"),
+        ];
+
+        for asset in assets {
+            let expected = match validate_utf8(asset) {
+                Ok(()) => asset.len(),
+                Err(e) => e.valid_up_to,
+            };
+            assert_eq!(validate_utf8_up_to(asset), expected);
         }
     }
 
     #[test]
-    fn faust() {
-        const FAUST: &str = include_str!("../assets/faust_213kb.txt");
-        assert!(validate_utf8(FAUST.as_bytes()).is_ok());
+    fn stream_validator_accepts_emoji_split_at_every_boundary() {
+        use super::Utf8StreamValidator;
+
+        // "😀" is F0 9F 98 80, a 4-byte sequence
+        let emoji = "😀".as_bytes();
+        for split in 1..emoji.len() {
+            let mut validator = Utf8StreamValidator::new();
+            assert_eq!(validator.feed(&emoji[..split]), Ok(()));
+            assert_eq!(validator.feed(&emoji[split..]), Ok(()));
+            assert_eq!(validator.finish(), Ok(()), "split at {split}");
+        }
     }
 
     #[test]
-    fn chinese() {
-        const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
-        assert!(validate_utf8(CHINESE.as_bytes()).is_ok());
+    fn stream_validator_accepts_char_split_across_many_small_feeds() {
+        use super::Utf8StreamValidator;
+
+        let emoji = "😀".as_bytes();
+        let mut validator = Utf8StreamValidator::new();
+        for byte in emoji {
+            assert_eq!(validator.feed(std::slice::from_ref(byte)), Ok(()));
+        }
+        assert_eq!(validator.finish(), Ok(()));
     }
 
     #[test]
-    fn latin_3kb() {
-        const LATIN_3KB: &str = include_str!("../assets/latin_3kb.txt");
-        assert!(validate_utf8(LATIN_3KB.as_bytes()).is_ok());
+    fn stream_validator_reports_error_at_absolute_offset() {
+        use super::Utf8StreamValidator;
+
+        let mut validator = Utf8StreamValidator::new();
+        assert_eq!(validator.feed(b"abc"), Ok(()));
+        assert_eq!(
+            validator.feed(b"d\x80e"),
+            Err(super::Utf8Error { valid_up_to: 4, error_len: Some(1), error_byte: Some(0x80) })
+        );
     }
 
     #[test]
-    fn english_99pct_ascii() {
-        const ENGLISH: &str = include_str!("../assets/english_971kb.txt");
-        assert!(validate_utf8(ENGLISH.as_bytes()).is_ok());
+    fn stream_validator_reports_error_straddling_the_seam() {
+        use super::Utf8StreamValidator;
+
+        // valid lead byte followed by an invalid continuation byte, split
+        // right at the boundary between two feeds
+        let mut validator = Utf8StreamValidator::new();
+        assert_eq!(validator.feed(b"ab\xE2"), Ok(()));
+        assert_eq!(
+            validator.feed(b"\x28c"),
+            Err(super::Utf8Error { valid_up_to: 2, error_len: Some(1), error_byte: Some(0x28) })
+        );
+    }
+
+    #[test]
+    fn stream_validator_finish_rejects_dangling_partial_sequence() {
+        use super::Utf8StreamValidator;
+
+        let mut validator = Utf8StreamValidator::new();
+        assert_eq!(validator.feed(b"ab\xE2\x82"), Ok(()));
+        assert_eq!(
+            validator.finish(),
+            Err(super::Utf8Error { valid_up_to: 2, error_len: None, error_byte: None })
+        );
+    }
+
+    #[test]
+    fn stream_validator_finish_accepts_when_nothing_pending() {
+        use super::Utf8StreamValidator;
+
+        let mut validator = Utf8StreamValidator::new();
+        assert_eq!(validator.feed(b"hello"), Ok(()));
+        assert_eq!(validator.finish(), Ok(()));
     }
 }
@@ -29,6 +29,21 @@ fn main() {
     println!("success ratio 8x: {}", stats.success_ratio_8x());
     println!("success ratio 2x: {}", stats.success_ratio_2x());
     println!("ratio 8x to 2x: {}", stats.success_ratio_8x());
+
+    let mut optimized_stats = Statistics::default();
+    let mut reference_stats = Statistics::default();
+    assert!(
+        fast_utf8::validate_utf8_with_stats(ENGLISH_406.as_bytes(), Some(&mut optimized_stats))
+            .is_ok()
+    );
+    assert!(fast_utf8::validate_utf8_std_with_stats(
+        ENGLISH_406.as_bytes(),
+        Some(&mut reference_stats)
+    )
+    .is_ok());
+    println!("==========english/406kb, optimized vs. reference alignment behavior==========");
+    println!("optimized: unaligned_blocks={}, bytewise_checks={}", optimized_stats.unaligned_blocks, optimized_stats.bytewise_checks);
+    println!("reference: unaligned_blocks={}, bytewise_checks={}", reference_stats.unaligned_blocks, reference_stats.bytewise_checks);
 }
 
 /*
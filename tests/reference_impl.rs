@@ -0,0 +1,34 @@
+//! Correctness check for the `reference-impl` feature's scalar baseline.
+//!
+//! `cargo test` can't assert that a symbol is *absent* from a build;
+//! `validate_utf8_std`, `validate_utf8_std_with_stats`, and
+//! `validate_utf8_differential` simply don't exist as items unless
+//! `reference-impl` is enabled, so `cargo build --no-default-features`
+//! (part of every change's quality gate) already proves they compile out
+//! of a release-style build. This test only guards that, when the
+//! feature *is* on, the reference implementation still agrees with the
+//! optimized one.
+#![cfg(feature = "reference-impl")]
+
+#[test]
+fn std_agrees_with_default_validator_on_ascii() {
+    let text = "the quick brown fox jumps over the lazy dog";
+    assert_eq!(
+        fast_utf8::validate_utf8_std(text.as_bytes()).is_ok(),
+        fast_utf8::validate_utf8(text.as_bytes()).is_ok(),
+    );
+}
+
+#[test]
+fn std_agrees_with_default_validator_on_multibyte() {
+    let text = "こんにちは, café, Привет";
+    assert_eq!(
+        fast_utf8::validate_utf8_std(text.as_bytes()).is_ok(),
+        fast_utf8::validate_utf8(text.as_bytes()).is_ok(),
+    );
+}
+
+#[test]
+fn std_rejects_invalid_utf8() {
+    assert!(fast_utf8::validate_utf8_std(b"ab\xFF").is_err());
+}
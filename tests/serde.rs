@@ -0,0 +1,36 @@
+//! Round-trip check for the `serde` feature's `Serialize`/`Deserialize`
+//! derives on [`fast_utf8::Statistics`] and [`fast_utf8::Utf8Error`].
+#![cfg(feature = "serde")]
+
+use fast_utf8::{Statistics, Utf8Error};
+
+#[test]
+fn statistics_round_trips_through_json() {
+    let stats = Statistics {
+        success_blocks_8x: 1,
+        failed_blocks_8x: 2,
+        success_blocks_4x: 3,
+        failed_blocks_4x: 4,
+        success_blocks_2x: 5,
+        failed_blocks_2x: 6,
+        unaligned_blocks: 7,
+        bytewise_checks: 8,
+        non_ascii_checks: 9,
+        optimistic_2x_to_8x: 10,
+    };
+
+    let json = serde_json::to_string(&stats).unwrap();
+    let round_tripped: Statistics = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(stats, round_tripped);
+}
+
+#[test]
+fn utf8_error_round_trips_through_json() {
+    let err = Utf8Error::new(3, Some(1));
+
+    let json = serde_json::to_string(&err).unwrap();
+    let round_tripped: Utf8Error = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(err, round_tripped);
+}
@@ -0,0 +1,33 @@
+//! Correctness check for the `size-min` feature's table-free validator.
+//!
+//! `cargo test` can't assert on binary size directly; the actual size
+//! budget (well under `validate_utf8_std`'s 474B, see the doc comment on
+//! `validate_utf8_size_min`) is meant to be checked with
+//! `cargo bloat --release --features size-min --crates`. This test only
+//! guards that the branch-based `size-min` path agrees with the default
+//! validator, so the size/throughput tradeoff doesn't silently also
+//! become a correctness regression.
+#![cfg(feature = "size-min")]
+
+#[test]
+fn size_min_agrees_with_default_validator_on_ascii() {
+    let text = "the quick brown fox jumps over the lazy dog";
+    assert_eq!(
+        fast_utf8::validate_utf8_size_min(text.as_bytes()).is_ok(),
+        fast_utf8::validate_utf8(text.as_bytes()).is_ok(),
+    );
+}
+
+#[test]
+fn size_min_agrees_with_default_validator_on_multibyte() {
+    let text = "こんにちは, café, Привет";
+    assert_eq!(
+        fast_utf8::validate_utf8_size_min(text.as_bytes()).is_ok(),
+        fast_utf8::validate_utf8(text.as_bytes()).is_ok(),
+    );
+}
+
+#[test]
+fn size_min_rejects_invalid_utf8() {
+    assert!(fast_utf8::validate_utf8_size_min(b"ab\xFF").is_err());
+}
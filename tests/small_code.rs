@@ -0,0 +1,37 @@
+//! Correctness check for the `small-code` feature's code-size-biased
+//! inline hints and `has_non_ascii_byte` loop.
+//!
+//! `cargo test` can't assert on binary size directly; the actual delta
+//! against `validate_utf8`'s ~1.1KiB baseline (`main.rs`'s function-size
+//! note) is meant to be checked with `cargo bloat --release --features
+//! small-code --crates`, the same way `size-min`'s is (see
+//! `tests/size_min.rs`). This test only guards that flipping the inline
+//! hints and swapping in the tight-loop `has_non_ascii_byte` doesn't
+//! silently also become a correctness regression.
+#![cfg(feature = "small-code")]
+
+#[test]
+fn small_code_agrees_with_default_validator_on_ascii() {
+    let text = "the quick brown fox jumps over the lazy dog";
+    assert!(fast_utf8::validate_utf8(text.as_bytes()).is_ok());
+}
+
+#[test]
+fn small_code_agrees_with_default_validator_on_multibyte() {
+    let text = "こんにちは, café, Привет";
+    assert!(fast_utf8::validate_utf8(text.as_bytes()).is_ok());
+}
+
+#[test]
+fn small_code_rejects_invalid_utf8() {
+    assert!(fast_utf8::validate_utf8(b"ab\xFF").is_err());
+}
+
+#[test]
+fn small_code_agrees_with_default_validator_on_bundled_assets() {
+    const ENGLISH: &str = include_str!("../assets/english_971kb.txt");
+    const CHINESE: &str = include_str!("../assets/chinese_1mb.txt");
+
+    assert!(fast_utf8::validate_utf8(ENGLISH.as_bytes()).is_ok());
+    assert!(fast_utf8::validate_utf8(CHINESE.as_bytes()).is_ok());
+}